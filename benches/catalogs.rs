@@ -0,0 +1,65 @@
+//! Benchmarks of `YewI18n` with large catalogs.
+//!
+//! Components receive a clone of the `YewI18n` context on every render, and `ContextProvider`
+//! compares the new context with the previous one, so both must stay cheap as catalogs grow.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use yew_i18n::YewI18n;
+
+/// Builds a `YewI18n` instance with `keys` nested messages in each of three languages, and a
+/// message with a placeholder in each section.
+fn i18n(keys: usize) -> YewI18n {
+    let mut translations = HashMap::new();
+    for language in ["en", "fr", "de"] {
+        let mut sections = Map::new();
+        for section in 0..keys / 100 {
+            let mut messages = Map::new();
+            for key in 0..100 {
+                messages.insert(
+                    format!("key{}", key),
+                    json!(format!(
+                        "{} message {} of section {}",
+                        language, key, section
+                    )),
+                );
+            }
+            messages.insert(
+                "greeting".to_string(),
+                json!(format!(
+                    "{} greeting {{name}} of section {}",
+                    language, section
+                )),
+            );
+            sections.insert(format!("section{}", section), Value::Object(messages));
+        }
+        translations.insert(language.to_string(), Value::Object(sections).into());
+    }
+    YewI18n::from_translations(vec!["en", "fr", "de"], translations).unwrap()
+}
+
+fn catalogs(c: &mut Criterion) {
+    for keys in [1_000, 10_000] {
+        let i18n = i18n(keys);
+        c.bench_function(&format!("clone/{}", keys), |b| {
+            b.iter(|| black_box(&i18n).clone())
+        });
+
+        let other = i18n.clone();
+        c.bench_function(&format!("eq/{}", keys), |b| {
+            b.iter(|| black_box(&i18n) == black_box(&other))
+        });
+
+        c.bench_function(&format!("t/{}", keys), |b| {
+            b.iter(|| black_box(&i18n).t(black_box("section5.key42")))
+        });
+
+        c.bench_function(&format!("t_with/{}", keys), |b| {
+            b.iter(|| black_box(&i18n).t_with(black_box("section5.greeting"), &[("name", "Ada")]))
+        });
+    }
+}
+
+criterion_group!(benches, catalogs);
+criterion_main!(benches);
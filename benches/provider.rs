@@ -0,0 +1,97 @@
+//! Benchmarks of the `I18nProvider` render path with large catalogs.
+//!
+//! Yew clones the props of the provider and compares them with the previous ones on every
+//! render of its parent, so both must stay cheap as catalogs grow.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::{BTreeMap, HashMap};
+use yew::prelude::*;
+use yew_i18n::{Catalog, Translations, YewI18nProviderConfig};
+
+/// Builds `keys` nested messages in each of three languages.
+fn translations(keys: usize) -> Translations {
+    let mut translations = HashMap::new();
+    for language in ["en", "fr", "de"] {
+        let sections: BTreeMap<String, Catalog> = (0..keys / 100)
+            .map(|section| {
+                let messages = (0..100)
+                    .map(|key| {
+                        let message =
+                            format!("{} message {} of section {}", language, key, section);
+                        (format!("key{}", key), Catalog::from(message))
+                    })
+                    .collect();
+                (format!("section{}", section), Catalog::Table(messages))
+            })
+            .collect();
+        translations.insert(language.to_string(), Catalog::Table(sections));
+    }
+    translations.into()
+}
+
+fn props(translations: Translations) -> YewI18nProviderConfig {
+    YewI18nProviderConfig {
+        supported_languages: vec!["en", "fr", "de"],
+        translations,
+        binary: None,
+        source: None,
+        loader: None,
+        language: None,
+        namespaces: Vec::new(),
+        fallback: None,
+        on_error: None,
+        suspense: false,
+        children: html! { <p>{ "Hello" }</p> },
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    translations: Translations,
+}
+
+#[cfg(feature = "ssr")]
+#[function_component]
+fn App(props: &AppProps) -> Html {
+    html! {
+        <yew_i18n::I18nProvider supported_languages={vec!["en", "fr", "de"]} translations={props.translations.clone()} suspense=true>
+            <p>{ "Hello" }</p>
+        </yew_i18n::I18nProvider>
+    }
+}
+
+fn provider(c: &mut Criterion) {
+    for keys in [1_000, 10_000] {
+        let translations = translations(keys);
+        let props = props(translations.clone());
+
+        c.bench_function(&format!("provider_props/clone/{}", keys), |b| {
+            b.iter(|| black_box(&props).clone())
+        });
+
+        let other = props.clone();
+        c.bench_function(&format!("provider_props/eq/{}", keys), |b| {
+            b.iter(|| black_box(&props) == black_box(&other))
+        });
+
+        #[cfg(feature = "ssr")]
+        {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            c.bench_function(&format!("provider_render/{}", keys), |b| {
+                b.iter(|| {
+                    let props = AppProps {
+                        translations: translations.clone(),
+                    };
+                    let renderer = yew::LocalServerRenderer::<App>::with_props(props);
+                    runtime.block_on(tokio::task::LocalSet::new().run_until(renderer.render()))
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(benches, provider);
+criterion_main!(benches);
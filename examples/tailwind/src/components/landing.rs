@@ -34,13 +34,13 @@ pub fn trending_component() -> Html {
     let posts = vec![
         Post {
             id: 1,
-            title: Box::leak(i18n.t("Rust: The Next Big Thing in Data Science").into_boxed_str()),
+            title: i18n.t("Rust: The Next Big Thing in Data Science").into_owned().into(),
             url: "https://towardsdatascience.com/rust-the-next-big-thing-in-data-science-319a03305883",
-            date: Box::leak(i18n.t("24 Apr, 2023").into_boxed_str()),
+            date: i18n.t("24 Apr, 2023").into_owned().into(),
             thumb: "https://miro.medium.com/v2/resize:fit:720/format:webp/1*2jSP2n1KukVJYKVg2u4RuA.png",
             tags: vec![
                 Tag {
-                    name: Box::leak(i18n.t("Data Science").into_boxed_str()),
+                    name: i18n.t("Data Science").into_owned().into(),
                     url: "https://wiseai.dev/blog/tags/data-science",
                 },
             ],
@@ -51,13 +51,13 @@ pub fn trending_component() -> Html {
         },
         Post {
             id: 2,
-            title: Box::leak(i18n.t("The Ultimate Ndarray Handbook: Mastering the Art of Scientific Computing with Rust").into_boxed_str()),
+            title: i18n.t("The Ultimate Ndarray Handbook: Mastering the Art of Scientific Computing with Rust").into_owned().into(),
             url: "https://towardsdatascience.com/the-ultimate-ndarray-handbook-mastering-the-art-of-scientific-computing-with-rust-ef5ab767212a",
-            date: Box::leak(i18n.t("02 May, 2023").into_boxed_str()),
+            date: i18n.t("02 May, 2023").into_owned().into(),
             thumb: "https://miro.medium.com/v2/resize:fit:720/format:webp/1*bgmO2hUgZXpCHPC1XaBy3w.png",
             tags: vec![
                 Tag {
-                    name: Box::leak(i18n.t("Data Science").into_boxed_str()),
+                    name: i18n.t("Data Science").into_owned().into(),
                     url: "https://wiseai.dev/blog/tags/data-science",
                 },
             ],
@@ -68,13 +68,13 @@ pub fn trending_component() -> Html {
         },
         Post {
             id: 3,
-            title: Box::leak(i18n.t("Rust Polars: Unlocking High-Performance Data Analysis — Part 1").into_boxed_str()),
+            title: i18n.t("Rust Polars: Unlocking High-Performance Data Analysis — Part 1").into_owned().into(),
             url: "https://towardsdatascience.com/rust-polars-unlocking-high-performance-data-analysis-part-1-ce42af370ece",
-            date: Box::leak(i18n.t("11 May, 2023").into_boxed_str()),
+            date: i18n.t("11 May, 2023").into_owned().into(),
             thumb: "https://miro.medium.com/v2/resize:fit:720/0*Le8YYCDuEhc4A7tN",
             tags: vec![
                 Tag {
-                    name: Box::leak(i18n.t("Data Science").into_boxed_str()),
+                    name: i18n.t("Data Science").into_owned().into(),
                     url: "https://wiseai.dev/blog/tags/data-science",
                 },
             ],
@@ -106,7 +106,7 @@ pub fn trending_component() -> Html {
                     class={SELECT_CLASS}
                 >
                     <option value="en" selected=true hidden=true>{ "Select Language" }</option>
-                    { for i18n.config.supported_languages.iter().map(|&lang| render_language_option(lang)) }
+                    { for i18n.supported_languages().iter().map(|&lang| render_language_option(lang)) }
                 </select>
                 <div class={GRID_CONTAINER}>
                     { for posts.iter().map(|post| html! { <PostCard ..post.clone() /> }) }
@@ -137,8 +137,8 @@ pub fn post_card(post: &Post) -> Html {
             <div class="p-4">
                 <div class={DATE_TEXT}>{ &post.date }</div>
                 <h2 class={TITLE_TEXT}>
-                    <a href={post.url} title={post.title} target="_blank" rel="noreferrer">
-                        { post.title }
+                    <a href={post.url} title={post.title.clone()} target="_blank" rel="noreferrer">
+                        { post.title.clone() }
                     </a>
                 </h2>
                 <div class="flex space-x-2">
@@ -180,17 +180,17 @@ pub fn pagetitle(title: &str) -> Html {
 #[derive(Clone, Properties, PartialEq)]
 pub struct Post {
     pub id: usize,
-    pub title: &'static str,
+    pub title: AttrValue,
     pub thumb: &'static str,
     pub url: &'static str,
-    pub date: &'static str,
+    pub date: AttrValue,
     pub tags: Vec<Tag>,
     pub author: Author,
 }
 
 #[derive(Clone, Properties, PartialEq)]
 pub struct Tag {
-    pub name: &'static str,
+    pub name: AttrValue,
     pub url: &'static str,
 }
 
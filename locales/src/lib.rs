@@ -0,0 +1,148 @@
+//! Reads a directory of JSON catalogs.
+//!
+//! This is the reader shared by the `include_translations!` and `messages!` macros of
+//! `yew-i18n-macros` and the `yew-i18n-catalog` binary of `yew-i18n`, so both accept the same
+//! layouts. It is an implementation detail of those crates.
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The catalog of one language of a directory.
+///
+/// A language is either a `<lang>.json` file, or a `<lang>/` directory holding one
+/// `<namespace>.json` file per namespace.
+pub struct CatalogFile {
+    /// The language code, taken from the file stem or directory name.
+    pub language: String,
+    /// The files of the catalog, with their namespace for namespaced directories.
+    pub sources: Vec<(Option<String>, PathBuf)>,
+    /// The parsed catalog, with namespaces as top-level objects.
+    pub value: Value,
+}
+
+/// Reads and validates every `<lang>.json` file and `<lang>/` namespace directory in a
+/// directory, sorted by language.
+///
+/// Subdirectories whose name is not a language tag, such as `.git` or `assets`, are skipped.
+/// A language given both as a file and as a directory is an error.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs;
+///
+/// let dir = std::env::temp_dir().join(format!("yew-i18n-locales-{}", std::process::id()));
+/// fs::create_dir_all(dir.join("fr")).unwrap();
+/// fs::create_dir_all(dir.join("assets")).unwrap();
+/// fs::write(dir.join("en.json"), r#"{ "greeting": "Hello" }"#).unwrap();
+/// fs::write(dir.join("fr").join("common.json"), r#"{ "greeting": "Bonjour" }"#).unwrap();
+///
+/// let files = yew_i18n_locales::read_dir(&dir).unwrap();
+/// let languages: Vec<&str> = files.iter().map(|file| file.language.as_str()).collect();
+/// assert_eq!(languages, ["en", "fr"]);
+///
+/// fs::create_dir_all(dir.join("en")).unwrap();
+/// assert!(yew_i18n_locales::read_dir(&dir).is_err());
+/// # fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn read_dir(dir: &Path) -> Result<Vec<CatalogFile>, String> {
+    let mut files: Vec<CatalogFile> = Vec::new();
+    for path in json_entries(dir, true)? {
+        let name = if path.is_dir() {
+            path.file_name()
+        } else {
+            path.file_stem()
+        };
+        let Some(language) = name.and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if path.is_dir() && !is_language_tag(language) {
+            continue;
+        }
+        if files.iter().any(|file| file.language == language) {
+            return Err(format!(
+                "The language '{}' is given both as '{}.json' and as '{}/' in '{}'",
+                language,
+                language,
+                language,
+                dir.display()
+            ));
+        }
+        let language = language.to_string();
+
+        if path.is_dir() {
+            let mut sources = Vec::new();
+            let mut namespaces = serde_json::Map::new();
+            for path in json_entries(&path, false)? {
+                let Some(namespace) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                namespaces.insert(namespace.to_string(), read_file(&path)?);
+                sources.push((Some(namespace.to_string()), path));
+            }
+            files.push(CatalogFile {
+                language,
+                sources,
+                value: Value::Object(namespaces),
+            });
+        } else {
+            let value = read_file(&path)?;
+            files.push(CatalogFile {
+                language,
+                sources: vec![(None, path)],
+                value,
+            });
+        }
+    }
+
+    if files.is_empty() {
+        return Err(format!(
+            "No translation files found in '{}', expected files such as 'en.json'",
+            dir.display()
+        ));
+    }
+
+    files.sort_by(|a, b| a.language.cmp(&b.language));
+    Ok(files)
+}
+
+/// Returns whether a name is a language tag, such as `en`, `pt-BR` or `zh_Hant`.
+fn is_language_tag(name: &str) -> bool {
+    let mut subtags = name.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Lists the `.json` files of a directory, and its subdirectories if `with_dirs` is set.
+fn json_entries(dir: &Path, with_dirs: bool) -> Result<Vec<PathBuf>, String> {
+    let error = |err| format!("Unable to read the directory '{}': {}", dir.display(), err);
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(error)? {
+        let path = entry.map_err(error)?.path();
+        let is_json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+        if (is_json && path.is_file()) || (with_dirs && path.is_dir()) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read '{}': {}", path.display(), err))?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|err| format!("Invalid JSON in '{}': {}", path.display(), err))?;
+    if !value.is_object() {
+        return Err(format!(
+            "The translation file '{}' must contain a JSON object",
+            path.display()
+        ));
+    }
+    Ok(value)
+}
@@ -0,0 +1,143 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+pub use yew_i18n_locales::{read_dir, CatalogFile};
+
+/// Settings for macros that check keys against the catalogs of the invoking crate.
+///
+/// They are read from environment variables at compile time, which can be set in the
+/// `[env]` section of `.cargo/config.toml`:
+///
+/// * `YEW_I18N_LOCALES_DIR` - The catalog directory, `locales` by default.
+/// * `YEW_I18N_DEFAULT_LANGUAGE` - The language every key must exist in, `en` by default.
+/// * `YEW_I18N_CHECK_ALL_LANGUAGES` - Set to `1` or `true` to check every language.
+pub struct Settings {
+    pub dir: PathBuf,
+    pub default_language: String,
+    pub check_all_languages: bool,
+}
+
+impl Settings {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        Settings {
+            dir: resolve(&var("YEW_I18N_LOCALES_DIR").unwrap_or_else(|| "locales".to_string())),
+            default_language: var("YEW_I18N_DEFAULT_LANGUAGE").unwrap_or_else(|| "en".to_string()),
+            check_all_languages: matches!(
+                var("YEW_I18N_CHECK_ALL_LANGUAGES").as_deref(),
+                Some("1" | "true")
+            ),
+        }
+    }
+
+    /// Reads the catalogs to check, default language first.
+    pub fn catalogs(&self) -> Result<Vec<CatalogFile>, String> {
+        let mut files = read_dir(&self.dir)?;
+        let index = files
+            .iter()
+            .position(|file| file.language == self.default_language)
+            .ok_or_else(|| {
+                format!(
+                    "Unable to find '{}.json' in '{}'",
+                    self.default_language,
+                    self.dir.display()
+                )
+            })?;
+        let default = files.remove(index);
+        if !self.check_all_languages {
+            files.clear();
+        }
+        files.insert(0, default);
+        Ok(files)
+    }
+}
+
+/// Finds a message the same way `YewI18n::t` does: as a top-level key first, then as a
+/// `.`-separated path into nested objects, with a `namespace:` prefix standing for the
+/// `namespace.` one.
+pub fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    let find = |key: &str| {
+        value
+            .get(key)
+            .or_else(|| {
+                key.split('.')
+                    .try_fold(value, |value, part| value.get(part))
+            })
+            .filter(|value| !value.is_object())
+    };
+    find(key).or_else(|| {
+        let (namespace, key) = key.split_once(':')?;
+        find(&format!("{}.{}", namespace, key))
+    })
+}
+
+/// Lists every message of a catalog as `(key, message)` pairs, with nested keys joined by `.`.
+///
+/// Values other than strings and objects are messages holding their JSON text, as with
+/// `Catalog::from`.
+pub fn messages(value: &Value) -> Vec<(String, String)> {
+    fn walk(value: &Value, prefix: &str, messages: &mut Vec<(String, String)>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let key = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    walk(value, &key, messages);
+                }
+            }
+            Value::String(s) => messages.push((prefix.to_string(), s.clone())),
+            _ => messages.push((prefix.to_string(), value.to_string())),
+        }
+    }
+
+    let mut messages = Vec::new();
+    walk(value, "", &mut messages);
+    messages.sort();
+    messages
+}
+
+/// Returns the deduplicated placeholder names of a message, in order of first occurrence.
+pub fn placeholders(message: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = message;
+    while let Some(index) = rest.find(['{', '}']) {
+        let tail = &rest[index..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rest = &tail[2..];
+            continue;
+        }
+        rest = &tail[1..];
+        let Some(inner) = tail.strip_prefix('{') else {
+            continue;
+        };
+        let Some(end) = inner.find('}') else {
+            continue;
+        };
+        let name = inner[..end].trim();
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            if !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+            rest = &inner[end + 1..];
+        }
+    }
+    names
+}
+
+/// Resolves a path relative to the manifest directory of the crate being compiled.
+pub fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(path)
+}
@@ -0,0 +1,341 @@
+//! Procedural macros for the [`yew-i18n`](https://docs.rs/yew-i18n) crate.
+//!
+//! These macros are re-exported by `yew-i18n` when its `macros` feature is enabled, so
+//! you should not need to depend on this crate directly.
+
+mod catalog;
+mod messages;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Expr, Ident, LitStr, Token, Visibility};
+
+/// Embeds every `<lang>.json` file of a directory into the binary at compile time.
+///
+/// Namespaced catalogs can be split into `<lang>/<namespace>.json` files instead, which are
+/// embedded under the namespace key of the language catalog. Subdirectories whose name is not
+/// a language tag, such as `assets`, are skipped, and a language given both as a file and as a
+/// directory is a compile error. The path is relative to the
+/// `Cargo.toml` of the crate invoking the macro. Every file is
+/// parsed while compiling, so invalid JSON is reported as a compile error instead of at
+/// runtime. The macro expands to an `Arc<HashMap<String, yew_i18n::Catalog>>` keyed by the
+/// file stem, ready to be passed to `I18nProvider` or `YewI18n::from_translations`. The
+/// catalogs are built into the binary as code rather than JSON, so `serde_json` is not needed
+/// at runtime. They are built on the first call, and every later call from the same place
+/// returns the same shared catalogs, so calling the macro on every render does not copy them.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::{include_translations, Catalog};
+///
+/// let translations = include_translations!("locales");
+/// let message = |language: &str, path: &[&str]| {
+///     let catalog = path.iter().try_fold(&translations[language], |catalog, key| catalog.get(key));
+///     catalog.and_then(Catalog::as_message).map(str::to_string)
+/// };
+///
+/// assert_eq!(message("en", &["greeting"]).as_deref(), Some("Hello"));
+/// assert_eq!(message("fr", &["nav", "home"]).as_deref(), Some("Accueil"));
+///
+/// // Read from `locales/de/checkout.json`.
+/// assert_eq!(message("de", &["checkout", "title"]).as_deref(), Some("Kasse"));
+///
+/// // Later calls share the catalogs of the first one.
+/// let load = || include_translations!("locales");
+/// assert!(std::sync::Arc::ptr_eq(&load(), &load()));
+/// ```
+#[proc_macro]
+pub fn include_translations(input: TokenStream) -> TokenStream {
+    let dir = parse_macro_input!(input as LitStr);
+
+    let files = match catalog::read_dir(&catalog::resolve(&dir.value())) {
+        Ok(files) => files,
+        Err(err) => return syn::Error::new(dir.span(), err).to_compile_error().into(),
+    };
+
+    let entries = files.iter().map(|file| {
+        let language = &file.language;
+        let sources = include_sources(file);
+        let catalog = catalog_tokens(&file.value);
+        quote! {
+            #(#sources)*
+            translations.insert(::std::string::String::from(#language), #catalog);
+        }
+    });
+
+    quote! {
+        {
+            static TRANSLATIONS: ::std::sync::OnceLock<
+                ::std::sync::Arc<
+                    ::std::collections::HashMap<::std::string::String, ::yew_i18n::Catalog>,
+                >,
+            > = ::std::sync::OnceLock::new();
+
+            ::std::sync::Arc::clone(TRANSLATIONS.get_or_init(|| {
+                let mut translations = ::std::collections::HashMap::new();
+                #(#entries)*
+                ::std::sync::Arc::new(translations)
+            }))
+        }
+    }
+    .into()
+}
+
+/// Returns the code building a catalog, with the conversions of `Catalog::from`.
+fn catalog_tokens(value: &serde_json::Value) -> proc_macro2::TokenStream {
+    match value {
+        serde_json::Value::Object(map) => {
+            let entries = map.iter().map(|(key, value)| {
+                let value = catalog_tokens(value);
+                quote! { (::std::string::String::from(#key), #value) }
+            });
+            quote! {
+                ::yew_i18n::Catalog::Table(::std::collections::BTreeMap::from([#(#entries),*]))
+            }
+        }
+        value => {
+            let message = match value {
+                serde_json::Value::String(message) => message.clone(),
+                value => value.to_string(),
+            };
+            quote! { ::yew_i18n::Catalog::Message(::std::string::String::from(#message)) }
+        }
+    }
+}
+
+struct Argument {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Argument { name, value })
+    }
+}
+
+struct Translate {
+    i18n: Expr,
+    key: LitStr,
+    args: Punctuated<Argument, Token![,]>,
+}
+
+impl Parse for Translate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let i18n = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse()?;
+        let args = if input.parse::<Option<Token![,]>>()?.is_some() {
+            Punctuated::parse_terminated(input)?
+        } else {
+            Punctuated::new()
+        };
+        Ok(Translate { i18n, key, args })
+    }
+}
+
+/// Translates a key that is checked against the catalogs at compile time.
+///
+/// `t!(i18n, "key", name = value)` expands to `i18n.t_with("/key", &[("name", ...)])`, where
+/// each value is formatted with `ToString`, and `t!(i18n, "key")` to `i18n.t_with("/key", &[])`,
+/// so `{{` and `}}` render as braces whether or not arguments are passed.
+/// Keys are absolute, since they are checked against whole catalogs, so the scope of an
+/// `I18nScope` does not apply to them.
+/// The macro fails to compile when the key is missing from the catalog of the default
+/// language, or when the arguments do not match the `{name}` placeholders of the message.
+///
+/// Catalogs are the `<lang>.json` files in the `locales` directory next to your
+/// `Cargo.toml`, and the default language is `en`. Both can be changed through the
+/// `YEW_I18N_LOCALES_DIR` and `YEW_I18N_DEFAULT_LANGUAGE` environment variables, for
+/// instance in the `[env]` section of `.cargo/config.toml`. Set
+/// `YEW_I18N_CHECK_ALL_LANGUAGES=1` to check every language instead of only the default.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::{t, YewI18n};
+///
+/// let translations = yew_i18n::include_translations!("locales");
+/// let i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+///
+/// assert_eq!(t!(i18n, "nav.home"), "Home");
+/// assert_eq!(t!(i18n, "nav:home"), "Home");
+/// assert_eq!(t!(i18n, "welcome", name = "Ada"), "Welcome back, Ada!");
+/// assert_eq!(t!(i18n, "braces"), "Write {name} to insert a name");
+/// ```
+///
+/// Unknown keys and mismatched arguments are rejected:
+///
+/// ```compile_fail
+/// # use yew_i18n::{t, YewI18n};
+/// # let translations = yew_i18n::include_translations!("locales");
+/// # let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+/// t!(i18n, "nav.hoem");
+/// ```
+///
+/// ```compile_fail
+/// # use yew_i18n::{t, YewI18n};
+/// # let translations = yew_i18n::include_translations!("locales");
+/// # let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+/// t!(i18n, "welcome", user = "Ada");
+/// ```
+#[proc_macro]
+pub fn t(input: TokenStream) -> TokenStream {
+    let Translate { i18n, key, args } = parse_macro_input!(input as Translate);
+
+    let sources = match check_key(&key.value(), &args) {
+        Ok(sources) => sources,
+        Err(err) => return syn::Error::new(key.span(), err).to_compile_error().into(),
+    };
+    // Embedding the catalogs makes Cargo check the key again when they change.
+    let key = format!("/{}", key.value());
+    let key = quote! { { #(#sources)* #key } };
+    let args = args.iter().map(|Argument { name, value }| {
+        let name = name.to_string();
+        quote! { (#name, ::std::string::ToString::to_string(&(#value)).as_str()) }
+    });
+    quote! { (#i18n).t_with(#key, &[#(#args),*]) }.into()
+}
+
+/// Checks a key against the catalogs, returning the code embedding the files read.
+fn check_key(
+    key: &str,
+    args: &Punctuated<Argument, Token![,]>,
+) -> Result<Vec<proc_macro2::TokenStream>, String> {
+    let settings = catalog::Settings::from_env();
+    let mut expected: Vec<String> = args.iter().map(|arg| arg.name.to_string()).collect();
+    expected.sort();
+
+    let files = settings.catalogs()?;
+    for file in &files {
+        let message = catalog::lookup(&file.value, key).ok_or_else(|| {
+            format!(
+                "Unable to find the key '{}' in the language '{}'",
+                key, file.language
+            )
+        })?;
+        let mut placeholders = catalog::placeholders(message.as_str().unwrap_or_default());
+        placeholders.sort();
+        if placeholders != expected {
+            return Err(format!(
+                "The key '{}' in the language '{}' expects the arguments [{}], found [{}]",
+                key,
+                file.language,
+                placeholders.join(", "),
+                expected.join(", ")
+            ));
+        }
+    }
+
+    Ok(files.iter().flat_map(include_sources).collect())
+}
+
+/// Returns the code embedding the files of a catalog, so Cargo rebuilds the crate when they
+/// change.
+fn include_sources(file: &catalog::CatalogFile) -> Vec<proc_macro2::TokenStream> {
+    file.sources
+        .iter()
+        .map(|(_, path)| {
+            let path = path.to_string_lossy();
+            quote! { const _: &str = ::std::include_str!(#path); }
+        })
+        .collect()
+}
+
+struct Messages {
+    vis: Visibility,
+    name: Ident,
+    dir: Option<LitStr>,
+}
+
+impl Parse for Messages {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![mod]>()?;
+        let name = input.parse()?;
+        let dir = if input.parse::<Option<Token![=]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Messages { vis, name, dir })
+    }
+}
+
+/// Generates a module of strongly typed accessors for every message of the catalogs.
+///
+/// `messages!(pub mod msg)` reads the default language catalog the same way `t!` does and
+/// generates one function per key, named after the key in snake case. Messages with
+/// `{name}` placeholders take one `&str` argument per placeholder, in the order the
+/// placeholders first appear in the message. The module also contains
+/// a `Key` enum listing every key, for code that needs to store or iterate over keys.
+/// Append `= "path/to/locales"` to read another directory.
+///
+/// Renaming or removing a key in the catalog turns every outdated call site into a compile
+/// error, and IDEs can complete message names.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::{messages, YewI18n};
+///
+/// messages!(pub mod msg);
+///
+/// let translations = yew_i18n::include_translations!("locales");
+/// let mut i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+/// i18n.set_translation_language("fr").unwrap();
+///
+/// assert_eq!(msg::nav_home(&i18n), "Accueil");
+/// assert_eq!(msg::welcome(&i18n, "Ada"), "Bon retour, Ada !");
+/// assert_eq!(msg::Key::NavHome.as_str(), "nav.home");
+/// assert_eq!(i18n.t(msg::Key::Greeting.as_str()), "Bonjour");
+/// ```
+///
+/// Parameters are named after the placeholders in snake case, numbered when several
+/// placeholders share a name. Values other than strings are messages holding their JSON text,
+/// as at runtime:
+///
+/// ```
+/// use yew_i18n::{messages, Catalog, YewI18n};
+/// use std::collections::HashMap;
+///
+/// // `tests/placeholders/en.json` holds
+/// // { "invite": "{i18n} invited {user-name} and {user_name}", "empty": null,
+/// //   "transfer": "{user} sent {amount}", "braces": "Write {{name}}" }
+/// messages!(mod msg = "tests/placeholders");
+///
+/// let mut translations = HashMap::new();
+/// translations.insert("en".to_string(), Catalog::from([
+///     ("invite", "{i18n} invited {user-name} and {user_name}"),
+///     ("empty", "null"),
+///     ("transfer", "{user} sent {amount}"),
+///     ("braces", "Write {{name}}"),
+/// ]));
+/// let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+///
+/// assert_eq!(msg::invite(&i18n, "Ada", "Grace", "Alan"), "Ada invited Grace and Alan");
+/// assert_eq!(msg::empty(&i18n), "null");
+/// assert_eq!(msg::transfer(&i18n, "Ada", "10 €"), "Ada sent 10 €");
+/// assert_eq!(msg::braces(&i18n), "Write {name}");
+/// ```
+#[proc_macro]
+pub fn messages(input: TokenStream) -> TokenStream {
+    let Messages { vis, name, dir } = parse_macro_input!(input as Messages);
+
+    let mut settings = catalog::Settings::from_env();
+    settings.check_all_languages = false;
+    if let Some(dir) = &dir {
+        settings.dir = catalog::resolve(&dir.value());
+    }
+
+    match settings.catalogs() {
+        Ok(files) => messages::generate(&vis, &name, &files[0]).into(),
+        Err(err) => syn::Error::new(name.span(), err).to_compile_error().into(),
+    }
+}
@@ -0,0 +1,156 @@
+use crate::catalog::{self, CatalogFile};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::collections::HashSet;
+use syn::{Ident, Visibility};
+
+/// Generates the accessor module for the messages of a catalog.
+pub fn generate(vis: &Visibility, name: &Ident, file: &CatalogFile) -> TokenStream {
+    let messages = catalog::messages(&file.value);
+    let mut functions = HashSet::new();
+    let mut variants = HashSet::new();
+
+    let mut accessors = Vec::new();
+    let mut keys = Vec::new();
+    let mut variant_names = Vec::new();
+    let mut key_names = Vec::new();
+
+    for (key, message) in &messages {
+        let function = unique(&mut functions, snake_case(key));
+        let function = Ident::new(&function, Span::call_site());
+        let variant = unique(&mut variants, pascal_case(key));
+        let variant = Ident::new(&variant, Span::call_site());
+
+        let placeholders = catalog::placeholders(message);
+        // Placeholders differing only in `-` and `_` get distinct parameters.
+        let mut names = HashSet::new();
+        let params: Vec<Ident> = placeholders
+            .iter()
+            .map(|placeholder| {
+                let param = unique(&mut names, snake_case(placeholder));
+                Ident::new(&param, Span::call_site())
+            })
+            .collect();
+
+        let doc = format!(" `{}`: {}", key, message);
+        // Keys are absolute so accessors ignore the scope of an `I18nScope`.
+        let path = format!("/{}", key);
+        let body = quote! { __i18n.t_with(#path, &[#((#placeholders, #params)),*]) };
+
+        accessors.push(quote! {
+            #[doc = #doc]
+            pub fn #function<'a>(__i18n: &'a ::yew_i18n::YewI18n, #(#params: &str),*) -> ::std::borrow::Cow<'a, str> {
+                #body
+            }
+        });
+        keys.push(quote! {
+            #[doc = #doc]
+            #variant
+        });
+        key_names.push(quote! { Key::#variant => #key });
+        variant_names.push(variant);
+    }
+
+    // Embedding the catalog makes Cargo generate the accessors again when it changes.
+    let sources = crate::include_sources(file);
+
+    quote! {
+        #[allow(dead_code)]
+        #vis mod #name {
+            #(#sources)*
+
+            #(#accessors)*
+
+            /// Every translation key of the catalog.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum Key {
+                #(#keys),*
+            }
+
+            impl Key {
+                /// Every key, sorted by name.
+                pub const ALL: &'static [Key] = &[#(Key::#variant_names),*];
+
+                /// Returns the translation key as passed to `YewI18n::t`.
+                pub fn as_str(self) -> &'static str {
+                    match self {
+                        #(#key_names),*
+                    }
+                }
+            }
+
+            impl ::std::convert::AsRef<str> for Key {
+                fn as_ref(&self) -> &str {
+                    self.as_str()
+                }
+            }
+        }
+    }
+}
+
+/// Returns `name`, or `name` followed by a number if it is already taken.
+fn unique(taken: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut index = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{}_{}", name, index);
+        index += 1;
+    }
+    candidate
+}
+
+fn words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lower = false;
+    for c in key.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+            word.push(c.to_ascii_lowercase());
+        } else if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+            previous_lower = false;
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn snake_case(key: &str) -> String {
+    let name = words(key).join("_");
+    let name = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("m_{}", name)
+    } else {
+        name
+    };
+    if syn::parse_str::<Ident>(&name).is_ok() {
+        name
+    } else {
+        format!("{}_", name)
+    }
+}
+
+fn pascal_case(key: &str) -> String {
+    let name: String = words(key)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("M{}", name)
+    } else if name == "Self" {
+        "Self_".to_string()
+    } else {
+        name
+    }
+}
@@ -0,0 +1,50 @@
+//! Encodes a directory of JSON catalogs into a binary catalog.
+//!
+//! ```sh
+//! yew-i18n-catalog <locales dir> <output file>
+//! ```
+//!
+//! Reads the same layouts as `include_translations!`: one `<lang>.json` file per language,
+//! or one `<lang>/<namespace>.json` file per namespace.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use yew_i18n::Catalog;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [dir, output] = args.as_slice() else {
+        eprintln!("Usage: yew-i18n-catalog <locales dir> <output file>");
+        return ExitCode::FAILURE;
+    };
+    let result = read_catalogs(Path::new(dir)).and_then(|translations| {
+        let bytes = yew_i18n::binary::encode(&translations);
+        fs::write(output, &bytes)
+            .map_err(|err| format!("Unable to write '{}': {}", output, err))?;
+        Ok((translations.len(), bytes.len()))
+    });
+    match result {
+        Ok((languages, len)) => {
+            println!(
+                "Wrote {} languages to '{}' ({} bytes)",
+                languages, output, len
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads the catalog of every language of a directory.
+fn read_catalogs(dir: &Path) -> Result<HashMap<String, Catalog>, String> {
+    let files = yew_i18n_locales::read_dir(dir)?;
+    Ok(files
+        .into_iter()
+        .map(|file| (file.language, Catalog::from(file.value)))
+        .collect())
+}
@@ -0,0 +1,333 @@
+//! Compact binary catalogs.
+//!
+//! Pretty JSON catalogs and the parser needed to read them take a significant share of a
+//! WASM bundle. This module encodes catalogs into a compact binary format, where each
+//! distinct string is stored once, so keys repeated in every language and identical
+//! messages take no extra space. [`BinaryCatalog`] reads messages straight out of the
+//! encoded bytes, without parsing or copying them.
+//!
+//! Catalogs are flattened when encoded: nested tables become `.`-separated keys
+//! (`nav.home`). Produce the file
+//! at build time with the `yew-i18n-catalog` tool, or with [`encode`] from a build script,
+//! and embed it with `include_bytes!`:
+//!
+//! ```sh
+//! cargo run --features cli --bin yew-i18n-catalog -- locales/ locales.bin
+//! ```
+//!
+//! ```rust
+//! use yew_i18n::binary::{self, BinaryCatalog};
+//! use yew_i18n::Catalog;
+//! use std::collections::HashMap;
+//!
+//! let mut translations = HashMap::new();
+//! translations.insert("en".to_string(), Catalog::from([("nav", Catalog::from([("home", "Home")]))]));
+//! translations.insert("fr".to_string(), Catalog::from([("nav", Catalog::from([("home", "Accueil")]))]));
+//!
+//! // In an application: `BinaryCatalog::new(include_bytes!("../locales.bin"))`.
+//! let bytes = binary::encode(&translations);
+//! let catalog = BinaryCatalog::new(&bytes).unwrap();
+//! assert_eq!(catalog.get("fr", "nav.home"), Some("Accueil"));
+//! ```
+//!
+//! # Format
+//!
+//! All integers are little-endian `u32`s.
+//!
+//! * The magic bytes `YI18` and the format version, `1`.
+//! * The number of strings, followed by one more offset than there are strings, each the
+//!   start of a string in the string data, followed by the byte length of the string data and
+//!   the UTF-8 string data itself.
+//! * The number of languages, then for each language the index of its name in the strings,
+//!   its number of messages, and one pair of key and message string indices per message,
+//!   sorted by key.
+
+use crate::catalog::flatten;
+use crate::Catalog;
+use std::collections::{BTreeMap, HashMap};
+
+/// The magic bytes starting every binary catalog.
+const MAGIC: &[u8; 4] = b"YI18";
+/// The version of the format written by [`encode`].
+const VERSION: u32 = 1;
+
+/// Encodes catalogs into the binary format read by [`BinaryCatalog`].
+///
+/// # Arguments
+///
+/// * `translations` - Translations for different languages, as given to `I18nProvider`.
+///
+/// # Returns
+///
+/// The encoded catalogs. The output is deterministic, so it can be checked in or cached.
+pub fn encode(translations: &HashMap<String, Catalog>) -> Vec<u8> {
+    let mut strings = Strings::default();
+    let languages: BTreeMap<&String, Vec<(u32, u32)>> = translations
+        .iter()
+        .map(|(language, catalog)| {
+            let messages: BTreeMap<String, String> = flatten(catalog).into_iter().collect();
+            let entries = messages
+                .iter()
+                .map(|(key, message)| (strings.intern(key), strings.intern(message)))
+                .collect();
+            (language, entries)
+        })
+        .collect();
+    let names: Vec<u32> = languages.keys().map(|name| strings.intern(name)).collect();
+
+    let mut output = Vec::new();
+    output.extend_from_slice(MAGIC);
+    push(&mut output, VERSION);
+    push(&mut output, strings.offsets.len() as u32);
+    for offset in &strings.offsets {
+        push(&mut output, *offset);
+    }
+    // The extra offset ends the last string.
+    push(&mut output, strings.data.len() as u32);
+    push(&mut output, strings.data.len() as u32);
+    output.extend_from_slice(strings.data.as_bytes());
+    push(&mut output, languages.len() as u32);
+    for (name, entries) in names.into_iter().zip(languages.values()) {
+        push(&mut output, name);
+        push(&mut output, entries.len() as u32);
+        for (key, message) in entries {
+            push(&mut output, *key);
+            push(&mut output, *message);
+        }
+    }
+    output
+}
+
+fn push(output: &mut Vec<u8>, value: u32) {
+    output.extend_from_slice(&value.to_le_bytes());
+}
+
+/// The deduplicated strings of a catalog being encoded.
+#[derive(Default)]
+struct Strings {
+    data: String,
+    offsets: Vec<u32>,
+    indices: HashMap<String, u32>,
+}
+
+impl Strings {
+    fn intern(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.indices.get(string) {
+            return *index;
+        }
+        let index = self.offsets.len() as u32;
+        self.offsets.push(self.data.len() as u32);
+        self.data.push_str(string);
+        self.indices.insert(string.to_string(), index);
+        index
+    }
+}
+
+/// Catalogs read from the binary format produced by [`encode`], without copying them.
+///
+/// The bytes are validated once by [`BinaryCatalog::new`]. Looking up a message is a binary
+/// search over the keys of its language, and returns a string borrowed from the bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct BinaryCatalog<'a> {
+    /// The offsets of the strings, one more than there are strings.
+    offsets: &'a [u8],
+    strings: &'a str,
+    languages: &'a [u8],
+}
+
+impl<'a> BinaryCatalog<'a> {
+    /// Reads catalogs encoded by [`encode`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The encoded catalogs, typically embedded with `include_bytes!`.
+    ///
+    /// # Returns
+    ///
+    /// The catalogs, or an error message if the bytes are not a valid binary catalog.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::binary::{self, BinaryCatalog};
+    /// use yew_i18n::Catalog;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+    /// let mut bytes = binary::encode(&translations);
+    /// assert_eq!(BinaryCatalog::new(&bytes).unwrap().get("en", "greeting"), Some("Hello"));
+    ///
+    /// // The message of the last entry points past the strings.
+    /// let len = bytes.len();
+    /// bytes[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+    /// assert!(BinaryCatalog::new(&bytes).is_err());
+    /// ```
+    pub fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("Invalid binary catalog: {}", reason);
+        let mut reader = Reader(bytes);
+        if reader.take(4) != Some(MAGIC.as_slice()) {
+            return Err(invalid("missing magic bytes"));
+        }
+        match reader.u32() {
+            Some(VERSION) => {}
+            Some(version) => return Err(invalid(&format!("unsupported version {}", version))),
+            None => return Err(invalid("truncated header")),
+        }
+        let count = reader.u32().ok_or_else(|| invalid("truncated header"))? as usize;
+        let offsets = count
+            .checked_add(1)
+            .and_then(|len| len.checked_mul(4))
+            .and_then(|len| reader.take(len))
+            .ok_or_else(|| invalid("truncated string offsets"))?;
+        let len = reader.u32().ok_or_else(|| invalid("truncated strings"))? as usize;
+        let strings = reader
+            .take(len)
+            .ok_or_else(|| invalid("truncated strings"))?;
+        let strings = std::str::from_utf8(strings).map_err(|_| invalid("strings are not UTF-8"))?;
+        let catalog = BinaryCatalog {
+            offsets,
+            strings,
+            languages: reader.0,
+        };
+
+        for index in 0..count {
+            catalog
+                .string(index as u32)
+                .ok_or_else(|| invalid("string out of bounds"))?;
+        }
+        let mut languages = Reader(catalog.languages);
+        let count = languages
+            .u32()
+            .ok_or_else(|| invalid("truncated languages"))?;
+        for _ in 0..count {
+            let name = languages
+                .u32()
+                .ok_or_else(|| invalid("truncated languages"))?;
+            let entries = languages
+                .u32()
+                .ok_or_else(|| invalid("truncated languages"))?;
+            let valid = catalog.string(name).is_some()
+                && (0..entries).all(|_| {
+                    let key = languages.u32().and_then(|key| catalog.string(key));
+                    let message = languages.u32().and_then(|message| catalog.string(message));
+                    key.is_some() && message.is_some()
+                });
+            if !valid {
+                return Err(invalid("truncated messages"));
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// Returns the languages of the catalogs.
+    pub fn languages(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.language_tables().map(|(name, _)| name)
+    }
+
+    /// Returns whether the catalogs have a language.
+    pub fn contains_language(&self, language: &str) -> bool {
+        self.entries(language).is_some()
+    }
+
+    /// Returns the message of a `.`-separated key in a language.
+    pub fn get(&self, language: &str, key: &str) -> Option<&'a str> {
+        let entries = self.entries(language)?;
+        let index = self.search(entries, key).ok()?;
+        self.string(u32_at(entries, index * 8 + 4)?)
+    }
+
+    /// Returns the keys of a language, in sorted order.
+    pub fn keys(&self, language: &str) -> impl Iterator<Item = &'a str> + '_ {
+        let entries = self.entries(language).unwrap_or_default();
+        (0..entries.len() / 8).filter_map(move |index| self.string(u32_at(entries, index * 8)?))
+    }
+
+    /// Returns whether a language has a key starting with `prefix`.
+    pub(crate) fn contains_prefix(&self, language: &str, prefix: &str) -> bool {
+        let Some(entries) = self.entries(language) else {
+            return false;
+        };
+        let index = self.search(entries, prefix).unwrap_or_else(|index| index);
+        u32_at(entries, index * 8)
+            .and_then(|key| self.string(key))
+            .is_some_and(|key| key.starts_with(prefix))
+    }
+
+    /// Finds a key in the sorted entries of a language.
+    fn search(&self, entries: &[u8], key: &str) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, entries.len() / 8);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let candidate = u32_at(entries, middle * 8)
+                .and_then(|index| self.string(index))
+                .unwrap_or_default();
+            match candidate.cmp(key) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Ok(middle),
+            }
+        }
+        Err(low)
+    }
+
+    /// Returns the entries of a language.
+    fn entries(&self, language: &str) -> Option<&'a [u8]> {
+        self.language_tables()
+            .find(|(name, _)| *name == language)
+            .map(|(_, entries)| entries)
+    }
+
+    /// Returns the name and entries of each language.
+    fn language_tables(&self) -> impl Iterator<Item = (&'a str, &'a [u8])> + '_ {
+        let mut reader = Reader(self.languages);
+        let count = reader.u32().unwrap_or_default();
+        (0..count).map_while(move |_| {
+            let name = self.string(reader.u32()?)?;
+            let len = (reader.u32()? as usize).checked_mul(8)?;
+            Some((name, reader.take(len)?))
+        })
+    }
+
+    fn string(&self, index: u32) -> Option<&'a str> {
+        // Indexes come from the catalog, so they may overflow `usize` on 32-bit targets.
+        let index = (index as usize).checked_mul(4)?;
+        let start = u32_at(self.offsets, index)? as usize;
+        let end = match u32_at(self.offsets, index.checked_add(4)?)? as usize {
+            // The last offset is the byte length of the string data.
+            end if end >= start => end,
+            _ => return None,
+        };
+        self.strings.get(start..end)
+    }
+}
+
+impl PartialEq for BinaryCatalog<'_> {
+    /// Compares the catalogs by identity, as they are typically embedded in the binary.
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.offsets, other.offsets) && std::ptr::eq(self.strings, other.strings)
+    }
+}
+
+fn u32_at(bytes: &[u8], index: usize) -> Option<u32> {
+    let bytes = bytes.get(index..index.checked_add(4)?)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Reads the sections of a binary catalog in order.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        u32_at(self.take(4)?, 0)
+    }
+}
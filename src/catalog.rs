@@ -0,0 +1,329 @@
+//! Catalogs and their compiled storage.
+//!
+//! [`Catalog`] is the library-owned representation of the messages of a language. The first
+//! lookup in a language flattens its catalog into a map from full keys, such as `nav.home` or
+//! `checkout.title` for namespaces, to messages parsed once into text and placeholders. Later
+//! lookups are a single hash map access, without walking the tree or parsing the message again.
+
+use crate::message::Message;
+use crate::TranslationSource;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "router")]
+use std::sync::Mutex;
+use std::sync::{Arc, OnceLock};
+
+/// The messages of a language, grouped into nested tables.
+///
+/// Keys of nested tables are joined with `.` when looked up, so `t("nav.home")` finds the
+/// `home` message of the `nav` table. Namespaces are the top-level tables of a language.
+///
+/// With the default `json` feature, catalogs convert from and into `serde_json::Value`:
+/// strings become messages, objects become tables, and other values become messages holding
+/// their JSON text.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::Catalog;
+///
+/// let catalog = Catalog::from([
+///     ("greeting", Catalog::from("Hello")),
+///     ("nav", Catalog::from([("home", "Home")])),
+/// ]);
+/// assert_eq!(catalog.get("nav").and_then(|nav| nav.get("home")), Some(&Catalog::from("Home")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Catalog {
+    /// A message.
+    Message(String),
+    /// Nested catalogs by key.
+    Table(BTreeMap<String, Catalog>),
+}
+
+impl Catalog {
+    /// Returns the entry of a table under a key, without following `.`-separated paths.
+    pub fn get(&self, key: &str) -> Option<&Catalog> {
+        self.as_table()?.get(key)
+    }
+
+    /// Returns the text of a message.
+    pub fn as_message(&self) -> Option<&str> {
+        match self {
+            Catalog::Message(message) => Some(message),
+            Catalog::Table(_) => None,
+        }
+    }
+
+    /// Returns the entries of a table.
+    pub fn as_table(&self) -> Option<&BTreeMap<String, Catalog>> {
+        match self {
+            Catalog::Message(_) => None,
+            Catalog::Table(table) => Some(table),
+        }
+    }
+
+    /// Returns the entries of a table, turning a message into an empty table first.
+    pub fn table_mut(&mut self) -> &mut BTreeMap<String, Catalog> {
+        if let Catalog::Message(_) = self {
+            *self = Catalog::default();
+        }
+        match self {
+            Catalog::Table(table) => table,
+            Catalog::Message(_) => unreachable!("replaced by a table above"),
+        }
+    }
+}
+
+impl Default for Catalog {
+    /// Returns an empty table.
+    fn default() -> Self {
+        Catalog::Table(BTreeMap::new())
+    }
+}
+
+impl From<&str> for Catalog {
+    fn from(message: &str) -> Self {
+        Catalog::Message(message.to_string())
+    }
+}
+
+impl From<String> for Catalog {
+    fn from(message: String) -> Self {
+        Catalog::Message(message)
+    }
+}
+
+impl<K: Into<String>, V: Into<Catalog>, const N: usize> From<[(K, V); N]> for Catalog {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<Catalog>> FromIterator<(K, V)> for Catalog {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(entries: I) -> Self {
+        Catalog::Table(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Value> for Catalog {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(message) => Catalog::Message(message),
+            serde_json::Value::Object(map) => map.into_iter().collect(),
+            value => Catalog::Message(value.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<Catalog> for serde_json::Value {
+    fn from(catalog: Catalog) -> Self {
+        match catalog {
+            Catalog::Message(message) => serde_json::Value::String(message),
+            Catalog::Table(table) => serde_json::Value::Object(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The flattened catalog of a language.
+pub(crate) type FlatCatalog = HashMap<String, Message>;
+
+/// The keys under a table of a language, without the table, by their message.
+#[cfg(feature = "router")]
+pub(crate) type ReverseTable = HashMap<String, Vec<String>>;
+
+/// The catalogs of every language, shared by the `YewI18n` instances of a provider, and the
+/// sources looked up after them.
+#[derive(Default)]
+pub(crate) struct CatalogSet {
+    translations: Arc<HashMap<String, Catalog>>,
+    flat: HashMap<String, OnceLock<FlatCatalog>>,
+    sources: Vec<Arc<dyn TranslationSource>>,
+    /// The reverse tables built so far, by language and table.
+    #[cfg(feature = "router")]
+    reverse: Mutex<HashMap<(String, String), Arc<ReverseTable>>>,
+}
+
+/// A message found in the catalogs.
+pub(crate) enum Entry<'a> {
+    /// A message parsed from a catalog.
+    Message(&'a Message),
+    /// A message returned by a source.
+    Text(Cow<'a, str>),
+}
+
+impl<'a> Entry<'a> {
+    /// Fills in the placeholders of the message and unescapes its braces.
+    pub(crate) fn format(self, args: &[(&str, &str)]) -> Cow<'a, str> {
+        match self {
+            Entry::Message(message) => message.format(args),
+            Entry::Text(Cow::Borrowed(message)) => crate::message::format(message, args),
+            Entry::Text(Cow::Owned(message)) => {
+                Cow::Owned(crate::message::format(&message, args).into_owned())
+            }
+        }
+    }
+}
+
+impl CatalogSet {
+    pub(crate) fn new(
+        translations: Arc<HashMap<String, Catalog>>,
+        sources: Vec<Arc<dyn TranslationSource>>,
+    ) -> Self {
+        let flat = translations
+            .keys()
+            .map(|language| (language.clone(), OnceLock::new()))
+            .collect();
+        CatalogSet {
+            translations,
+            flat,
+            sources,
+            #[cfg(feature = "router")]
+            reverse: Mutex::default(),
+        }
+    }
+
+    /// Returns the in-memory catalogs, without those of the sources.
+    pub(crate) fn translations(&self) -> &HashMap<String, Catalog> {
+        &self.translations
+    }
+
+    /// Returns the catalog of a language.
+    #[cfg(feature = "yew")]
+    pub(crate) fn get(&self, language: &str) -> Option<&Catalog> {
+        self.translations.get(language)
+    }
+
+    #[cfg(feature = "yew")]
+    pub(crate) fn contains(&self, language: &str) -> bool {
+        self.translations.contains_key(language)
+            || self
+                .sources
+                .iter()
+                .any(|source| source.has_language(language))
+    }
+
+    /// Returns whether a language has messages under a namespace.
+    #[cfg(feature = "yew")]
+    pub(crate) fn contains_namespace(&self, language: &str, namespace: &str) -> bool {
+        self.get(language)
+            .and_then(|catalog| catalog.get(namespace))
+            .is_some()
+            || self
+                .sources
+                .iter()
+                .any(|source| source.has_namespace(language, namespace))
+    }
+
+    /// Returns the keys under a table of a language by their message, in the catalogs and the
+    /// sources, building the reverse table on first use.
+    ///
+    /// Tables may be nested in a namespace, so `routes` also matches `app.routes.cart`.
+    #[cfg(feature = "router")]
+    pub(crate) fn reverse(&self, language: &str, table: &str) -> Arc<ReverseTable> {
+        let mut reverse = self.reverse.lock().unwrap_or_else(|err| err.into_inner());
+        let key = (language.to_string(), table.to_string());
+        Arc::clone(
+            reverse
+                .entry(key)
+                .or_insert_with(|| Arc::new(self.build_reverse(language, table))),
+        )
+    }
+
+    #[cfg(feature = "router")]
+    fn build_reverse(&self, language: &str, table: &str) -> ReverseTable {
+        let prefix = format!("{}.", table);
+        let nested = format!(".{}", prefix);
+        let mut keys: Vec<String> = self
+            .flat(language)
+            .map(|flat| flat.keys().cloned().collect())
+            .unwrap_or_default();
+        for source in &self.sources {
+            keys.extend(source.keys(language));
+        }
+        let mut reverse = ReverseTable::new();
+        for key in keys {
+            let inner = match key.strip_prefix(&prefix) {
+                Some(inner) => inner,
+                None => match key.find(&nested) {
+                    Some(start) => &key[start + nested.len()..],
+                    None => continue,
+                },
+            };
+            let message = match self.entry(language, &key) {
+                Some(Entry::Message(message)) => message.source().to_string(),
+                Some(Entry::Text(message)) => message.into_owned(),
+                None => continue,
+            };
+            reverse.entry(message).or_default().push(inner.to_string());
+        }
+        reverse
+    }
+
+    /// Returns the message of a full key, from the sources last.
+    pub(crate) fn entry(&self, language: &str, key: &str) -> Option<Entry<'_>> {
+        self.flat(language)
+            .and_then(|flat| flat.get(key))
+            .map(Entry::Message)
+            .or_else(|| {
+                self.sources
+                    .iter()
+                    .find_map(|source| source.message(language, key))
+                    .map(Entry::Text)
+            })
+    }
+
+    /// Returns the flattened catalog of a language, compiling it on first use.
+    fn flat(&self, language: &str) -> Option<&FlatCatalog> {
+        let catalog = self.translations.get(language)?;
+        let flat = self.flat.get(language)?;
+        Some(flat.get_or_init(|| {
+            flatten(catalog)
+                .into_iter()
+                .map(|(key, message)| (key, Message::parse(message)))
+                .collect()
+        }))
+    }
+}
+
+/// Returns the messages of a catalog under their `.`-separated path.
+///
+/// Keys of the catalog itself take precedence over paths through nested tables, as in
+/// `YewI18n::t`.
+pub(crate) fn flatten(catalog: &Catalog) -> HashMap<String, String> {
+    let mut flat = HashMap::new();
+    flatten_into(catalog, None, &mut flat);
+    flat
+}
+
+fn flatten_into(catalog: &Catalog, prefix: Option<&str>, flat: &mut HashMap<String, String>) {
+    let Catalog::Table(table) = catalog else {
+        return;
+    };
+    for (key, value) in table {
+        let path = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, key),
+            None => key.clone(),
+        };
+        match value {
+            Catalog::Table(_) => flatten_into(value, Some(&path), flat),
+            Catalog::Message(message) => {
+                if prefix.is_none() || !flat.contains_key(&path) {
+                    flat.insert(path, message.clone());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Localized document head.
+//!
+//! [`I18nHead`] keeps the `<head>` of the page in sync with the current language: the `lang`
+//! attribute of `<html>`, the translated `<title>` and meta description, and one
+//! `<link rel="alternate" hreflang>` per supported language for search engines. Pages
+//! rendered on the server get the same tags from [`render_head`].
+
+use crate::{use_translation, YewI18n};
+use std::fmt::Write;
+use yew::prelude::*;
+
+/// Marks the alternate links added by [`I18nHead`] and [`render_head`], so they are replaced on
+/// language changes and after hydration.
+const ALTERNATE_MARKER: &str = "data-yew-i18n";
+
+/// Properties of [`I18nHead`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct I18nHeadProps {
+    /// Key of the translated page title.
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
+    /// Key of the translated meta description.
+    #[prop_or_default]
+    pub description: Option<AttrValue>,
+    /// Returns the URL of the page in a language, for the `hreflang` alternate links. No
+    /// alternate links are emitted without it.
+    #[prop_or_default]
+    pub alternate_url: Option<Callback<String, String>>,
+    /// Prefix of the alternate URLs, such as `https://example.com`. Search engines expect
+    /// fully-qualified alternate URLs.
+    #[prop_or_default]
+    pub base_url: Option<AttrValue>,
+}
+
+/// Sets the language, title, meta description and `hreflang` alternate links of the document.
+///
+/// The head is updated whenever the current language changes. The first supported language
+/// is also linked as `x-default`. A title or description whose key is missing is left as it
+/// is rather than replaced by an error message.
+///
+/// The component renders nothing, and only updates the head in the browser: Yew renders the
+/// body of the page, and effects do not run on the server. Write the output of
+/// [`render_head`] into the page template instead.
+///
+/// # Examples
+///
+/// ```
+/// use yew::prelude::*;
+/// use yew_i18n::I18nHead;
+///
+/// #[function_component]
+/// fn Landing() -> Html {
+///     let alternate_url = Callback::from(|language: String| format!("/{}/", language));
+///
+///     html! {
+///         <>
+///             <I18nHead
+///                 title="landing.title"
+///                 description="landing.description"
+///                 alternate_url={alternate_url}
+///                 base_url="https://example.com"
+///             />
+///             <main>{ "..." }</main>
+///         </>
+///     }
+/// }
+/// ```
+#[function_component]
+pub fn I18nHead(props: &I18nHeadProps) -> Html {
+    let i18n = use_translation();
+    let head = Head::new(&i18n, props);
+
+    use_effect_with(head, |head| head.apply());
+
+    Html::default()
+}
+
+/// Returns the tags set by [`I18nHead`], for the `<head>` of a page rendered on the server.
+///
+/// The title, meta description and alternate links are returned as HTML, and the `lang`
+/// attribute of `<html>` is the current language of `i18n`. The alternate links carry a
+/// `data-yew-i18n` attribute, so that `I18nHead` replaces them once the page is hydrated
+/// instead of adding a second set.
+///
+/// # Arguments
+///
+/// * `i18n` - The instance the page is rendered with.
+/// * `props` - The properties given to `I18nHead` by the page.
+///
+/// # Returns
+///
+/// The HTML of the tags, with missing keys left out.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use yew::prelude::*;
+/// use yew_i18n::{render_head, Catalog, I18nHeadProps, YewI18n};
+///
+/// let mut translations = HashMap::new();
+/// translations.insert("fr".to_string(), Catalog::from([("title", "Accueil & {{bienvenue}}")]));
+/// let i18n = YewI18n::from_translations(vec!["fr"], translations).unwrap();
+///
+/// let props = I18nHeadProps {
+///     title: Some("title".into()),
+///     description: Some("missing".into()),
+///     alternate_url: Some(Callback::from(|language: String| format!("/{}/", language))),
+///     base_url: Some("https://example.com".into()),
+/// };
+/// assert_eq!(
+///     render_head(&i18n, &props),
+///     concat!(
+///         "<title>Accueil &amp; {bienvenue}</title>",
+///         r#"<link rel="alternate" hreflang="x-default" href="https://example.com/fr/" data-yew-i18n>"#,
+///         r#"<link rel="alternate" hreflang="fr" href="https://example.com/fr/" data-yew-i18n>"#,
+///     ),
+/// );
+/// ```
+pub fn render_head(i18n: &YewI18n, props: &I18nHeadProps) -> String {
+    Head::new(i18n, props).to_html()
+}
+
+/// The localized head of the document.
+#[derive(PartialEq)]
+struct Head {
+    language: String,
+    title: Option<String>,
+    description: Option<String>,
+    alternates: Vec<(String, String)>,
+}
+
+impl Head {
+    fn new(i18n: &YewI18n, props: &I18nHeadProps) -> Self {
+        let language = i18n.current_language();
+        let translate = |key: &AttrValue| {
+            let entry = i18n.resolve(language, key).ok()?;
+            Some(entry.format(&[]).into_owned())
+        };
+
+        let mut alternates: Vec<(String, String)> = Vec::new();
+        if let Some(alternate_url) = &props.alternate_url {
+            let base_url = props.base_url.as_deref().unwrap_or_default();
+            for language in i18n.supported_languages() {
+                let href = format!("{}{}", base_url, alternate_url.emit(language.to_string()));
+                if alternates.is_empty() {
+                    alternates.push(("x-default".to_string(), href.clone()));
+                }
+                alternates.push((language.to_string(), href));
+            }
+        }
+
+        Head {
+            language: language.to_string(),
+            title: props.title.as_ref().and_then(translate),
+            description: props.description.as_ref().and_then(translate),
+            alternates,
+        }
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::new();
+        if let Some(title) = &self.title {
+            let _ = write!(html, "<title>{}</title>", escape(title));
+        }
+        if let Some(description) = &self.description {
+            let _ = write!(
+                html,
+                r#"<meta name="description" content="{}">"#,
+                escape(description)
+            );
+        }
+        for (language, href) in &self.alternates {
+            let _ = write!(
+                html,
+                r#"<link rel="alternate" hreflang="{}" href="{}" {}>"#,
+                escape(language),
+                escape(href),
+                ALTERNATE_MARKER
+            );
+        }
+        html
+    }
+
+    fn apply(&self) {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+
+        if let Some(html) = document.document_element() {
+            let _ = html.set_attribute("lang", &self.language);
+        }
+        if let Some(title) = &self.title {
+            document.set_title(title);
+        }
+        let Ok(Some(head)) = document.query_selector("head") else {
+            return;
+        };
+
+        if let Some(description) = &self.description {
+            let meta = match document.query_selector("meta[name=\"description\"]") {
+                Ok(Some(meta)) => Some(meta),
+                _ => document.create_element("meta").ok().and_then(|meta| {
+                    let _ = meta.set_attribute("name", "description");
+                    head.append_child(&meta).ok()?;
+                    Some(meta)
+                }),
+            };
+            if let Some(meta) = meta {
+                let _ = meta.set_attribute("content", description);
+            }
+        }
+
+        if let Ok(links) = document.query_selector_all(&format!("link[{}]", ALTERNATE_MARKER)) {
+            for index in 0..links.length() {
+                if let Some(link) = links.item(index) {
+                    let _ = head.remove_child(&link);
+                }
+            }
+        }
+        for (language, href) in &self.alternates {
+            let Ok(link) = document.create_element("link") else {
+                continue;
+            };
+            let _ = link.set_attribute("rel", "alternate");
+            let _ = link.set_attribute("hreflang", language);
+            let _ = link.set_attribute("href", href);
+            let _ = link.set_attribute(ALTERNATE_MARKER, "");
+            let _ = head.append_child(&link);
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
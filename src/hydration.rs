@@ -0,0 +1,177 @@
+//! Server-side rendering and hydration support.
+//!
+//! During server-side rendering, `I18nProvider` loads the catalogs of the requested language
+//! and sends them to the client along with the language through Yew's prepared state. The
+//! client picks both up while hydrating, so it renders the same language as the server
+//! without fetching or flashing the default language.
+//!
+//! The state is sent as JSON, so both features enable the `json` feature. Without it, the
+//! provider starts from its props.
+
+#[cfg(feature = "ssr")]
+use crate::loader::{merge_catalog, CatalogId};
+#[cfg(feature = "ssr")]
+use crate::TranslationLoader;
+use crate::{Catalog, YewI18nProviderConfig};
+#[cfg(feature = "ssr")]
+use serde_json::json;
+#[cfg(feature = "json")]
+use serde_json::Value;
+use std::collections::HashMap;
+#[cfg(feature = "json")]
+use yew::functional::use_prepared_state;
+use yew::prelude::*;
+use yew::suspense::SuspensionResult;
+
+/// The state rendered on the server, sent to the client for hydration.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct PreparedState {
+    /// The language the page was rendered in.
+    pub(crate) language: Option<String>,
+    /// The catalogs loaded on the server that are not part of the `translations` prop.
+    pub(crate) translations: HashMap<String, Catalog>,
+}
+
+impl PreparedState {
+    #[cfg(feature = "ssr")]
+    fn to_json(&self) -> String {
+        let translations: serde_json::Map<String, Value> = self
+            .translations
+            .iter()
+            .map(|(language, catalog)| (language.clone(), catalog.clone().into()))
+            .collect();
+        json!({
+            "language": self.language,
+            "translations": translations,
+        })
+        .to_string()
+    }
+
+    #[cfg(feature = "json")]
+    fn from_json(json: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(json).ok()?;
+        let language = value
+            .get("language")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let translations = value
+            .get("translations")
+            .and_then(Value::as_object)
+            .map(|translations| {
+                translations
+                    .iter()
+                    .map(|(language, catalog)| (language.clone(), catalog.clone().into()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(PreparedState {
+            language,
+            translations,
+        })
+    }
+}
+
+/// Returns the language the provider starts with: the `language` prop if it is supported,
+/// the first supported language otherwise.
+pub(crate) fn initial_language(props: &YewI18nProviderConfig) -> Option<String> {
+    props
+        .language
+        .as_deref()
+        .filter(|language| props.supported_languages.contains(language))
+        .or_else(|| props.supported_languages.first().copied())
+        .map(str::to_string)
+}
+
+/// Prepares the provider state on the server and restores it on the client.
+///
+/// On the server, the catalogs of the initial language missing from the `translations` prop
+/// are loaded with the provider's loader before rendering, unless it only runs in the
+/// browser, as HTTP loaders do. On a hydrating client, the state
+/// sent by the server is returned. Otherwise, `None` is returned.
+#[hook]
+pub(crate) fn use_prepared_catalogs(
+    props: &YewI18nProviderConfig,
+) -> SuspensionResult<Option<PreparedState>> {
+    #[cfg(feature = "ssr")]
+    let prepared = {
+        let language = initial_language(props);
+        let translations = props.translations.shared();
+        let source = props.source.clone();
+        let loader = props.loader.clone();
+        let namespaces: Vec<String> = props.namespaces.iter().map(|ns| ns.to_string()).collect();
+        use_prepared_state!((), async move |_| -> String {
+            prepare(language, translations, source, loader, namespaces)
+                .await
+                .to_json()
+        })?
+    };
+
+    #[cfg(all(feature = "json", not(feature = "ssr")))]
+    let prepared = {
+        let _ = props;
+        use_prepared_state!((), async move |_| -> String { String::new() })?
+    };
+
+    #[cfg(feature = "json")]
+    let prepared = prepared.and_then(|json| PreparedState::from_json(&json));
+
+    #[cfg(not(feature = "json"))]
+    let prepared = {
+        let _ = props;
+        None
+    };
+
+    Ok(prepared)
+}
+
+#[cfg(feature = "ssr")]
+async fn prepare(
+    language: Option<String>,
+    translations: std::sync::Arc<HashMap<String, Catalog>>,
+    source: Option<std::sync::Arc<dyn crate::TranslationSource>>,
+    loader: Option<TranslationLoader>,
+    namespaces: Vec<String>,
+) -> PreparedState {
+    let mut state = PreparedState {
+        language,
+        translations: HashMap::new(),
+    };
+    let Some(language) = state.language.clone() else {
+        return state;
+    };
+    // Like on the client, a source loads its missing languages when there is no loader.
+    // Loaders running only in the browser are left to the client.
+    let loader = loader.filter(TranslationLoader::runs_on_server);
+    let loader = match (loader, source) {
+        (Some(loader), _) => loader,
+        (None, Some(source)) if !source.has_language(&language) => {
+            TranslationLoader::from_source(source)
+        }
+        _ => return state,
+    };
+
+    let ids: Vec<CatalogId> = if loader.is_namespaced() {
+        namespaces
+            .into_iter()
+            .filter(|namespace| {
+                translations
+                    .get(&language)
+                    .and_then(|catalog| catalog.get(namespace))
+                    .is_none()
+            })
+            .map(|namespace| (language.clone(), Some(namespace)))
+            .collect()
+    } else if translations.contains_key(&language) {
+        Vec::new()
+    } else {
+        vec![(language.clone(), None)]
+    };
+
+    for id in ids {
+        // Catalogs failing to load on the server are loaded again by the client.
+        if let Ok(value) = loader.load_catalog(id.clone()).await {
+            merge_catalog(&mut state.translations, id, value);
+        }
+    }
+    state
+}
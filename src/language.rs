@@ -0,0 +1,89 @@
+//! Subtrees rendered in another language.
+//!
+//! An [`I18nLanguage`] renders its descendants in a given language without switching the
+//! language of the rest of the application, for "view this page in Español" prompts or
+//! bilingual legal text.
+
+use crate::{use_translation, YewI18n};
+use yew::prelude::*;
+
+/// Properties of [`I18nLanguage`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct I18nLanguageProps {
+    /// The language the children are rendered in.
+    pub language: AttrValue,
+    /// The components to render in the language.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Provides its children with a YewI18n context set to another language.
+///
+/// The current language of the enclosing provider is left unchanged. With a `loader`, the
+/// catalogs of the language are loaded as if it had been selected, and components using
+/// `use_translation_suspense` suspend until they are available. See [`YewI18n::t_in`] to
+/// translate a single key.
+///
+/// # Examples
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use std::collections::HashMap;
+/// use yew::prelude::*;
+/// use yew_i18n::{use_translation, Catalog, I18nLanguage, I18nProvider};
+///
+/// #[function_component]
+/// fn Terms() -> Html {
+///     let i18n = use_translation();
+///     html! { <p lang={i18n.current_language().to_string()}>{ i18n.t("terms") }</p> }
+/// }
+///
+/// #[function_component]
+/// fn App() -> Html {
+///     let mut translations = HashMap::new();
+///     translations.insert("en".to_string(), Catalog::from([("terms", "Terms of use")]));
+///     translations.insert("es".to_string(), Catalog::from([("terms", "Términos de uso")]));
+///
+///     html! {
+///         <I18nProvider supported_languages={vec!["en", "es"]} translations={translations} suspense=true>
+///             <Terms />
+///             <I18nLanguage language="es">
+///                 <Terms />
+///             </I18nLanguage>
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let html = yew::LocalServerRenderer::<App>::new().hydratable(false).render().await;
+/// assert!(html.contains(r#"<p lang="en">Terms of use</p><p lang="es">Términos de uso</p>"#));
+/// # }));
+/// ```
+#[function_component]
+pub fn I18nLanguage(props: &I18nLanguageProps) -> Html {
+    let i18n = use_translation_in(&props.language);
+
+    html! {
+        <ContextProvider<YewI18n> context={i18n}>{ props.children.clone() }</ContextProvider<YewI18n>>
+    }
+}
+
+/// Returns the YewI18n context set to another language, without switching the language of
+/// the enclosing provider.
+///
+/// The hook form of [`I18nLanguage`], for a single component.
+#[hook]
+pub fn use_translation_in(language: &str) -> YewI18n {
+    let mut i18n = use_translation();
+    // Like `t_in`, the language is used as is: a nested provider may find its messages in the
+    // catalogs of an enclosing one.
+    i18n.current_language = language.to_string();
+
+    {
+        let i18n = i18n.clone();
+        use_effect_with(i18n.required_catalogs(), move |_| i18n.request_load());
+    }
+
+    i18n
+}
@@ -20,6 +20,8 @@
 //! Here's a simple example of how to use it:
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "yew")]
+//! # {
 //! use yew::prelude::*;
 //! use std::collections::HashMap;
 //! use yew_i18n::{I18nProvider, YewI18nProviderConfig, use_translation};
@@ -42,6 +44,7 @@
 //!         </I18nProvider>
 //!     }
 //! }
+//! # }
 //! ```
 //!
 //! For more detailed information, check the [examples] provided in the library.
@@ -56,13 +59,23 @@
 //! for detailed configuration options.
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "yew")]
+//! # {
 //! use yew::prelude::*;
-//! use yew_i18n::{YewI18nProviderConfig, YewI18nConfig, YewI18n, I18nProvider};
+//! use yew_i18n::{YewI18nProviderConfig, YewI18n, I18nProvider};
 //! use std::collections::HashMap;
 //!
 //! let i18n_provider_config = YewI18nProviderConfig {
 //!     supported_languages: vec!["en", "fr"],
-//!     translations: HashMap::new(),
+//!     translations: HashMap::new().into(),
+//!     binary: None,
+//!     source: None,
+//!     language: None,
+//!     loader: None,
+//!     namespaces: vec![],
+//!     fallback: None,
+//!     on_error: None,
+//!     suspense: false,
 //!     children: html! { /* Your child components here... */ },
 //! };
 //!
@@ -73,8 +86,9 @@
 //! let supported_languages = vec!["en", "fr"];
 //! let translations = HashMap::new();
 //!
-//! let i18n = YewI18n::new(YewI18nConfig { supported_languages, translations: translations.clone()}, translations);
+//! let i18n = YewI18n::from_translations(supported_languages, translations);
 //! assert!(i18n.is_ok());
+//! # }
 //! ```
 //!
 //! ## Translation
@@ -84,6 +98,8 @@
 //! components.
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "yew")]
+//! # {
 //! use yew::prelude::*;
 //! use yew_i18n::use_translation;
 //!
@@ -98,8 +114,255 @@
 //!         <div>{ greeting }</div>
 //!     }
 //! }
+//! # }
 //! ```
 //!
+//! ## Embedded Catalogs
+//!
+//! With the `macros` feature enabled, `include_translations!` reads every `<lang>.json` file
+//! of a directory at compile time and expands to the shared translations map expected by
+//! `I18nProvider`:
+//!
+//! ```rust,ignore
+//! let translations = yew_i18n::include_translations!("locales/");
+//! ```
+//!
+//! The `t!` macro checks keys and interpolation arguments against those catalogs while
+//! compiling, so typos never reach the screen:
+//!
+//! ```rust,ignore
+//! let title = yew_i18n::t!(i18n, "nav.home");
+//! let welcome = yew_i18n::t!(i18n, "welcome", name = user.name);
+//! ```
+//!
+//! For full type safety, `messages!` generates a module with one function per key:
+//!
+//! ```rust,ignore
+//! yew_i18n::messages!(pub mod msg);
+//!
+//! let welcome = msg::welcome(&i18n, &user.name);
+//! ```
+//!
+//! ## Rich Text
+//!
+//! [`Trans`] renders messages containing tags, such as `Read the <link>terms</link>`, by
+//! replacing each tag with a component supplied by the caller, so the markup keeps its place
+//! in the sentence of every language:
+//!
+//! ```rust,ignore
+//! let mut components = HashMap::new();
+//! components.insert("link", Callback::from(|content: Html| html! { <a href="/terms">{ content }</a> }));
+//!
+//! html! { <Trans message="terms" components={components} /> }
+//! ```
+//!
+//! Translations holding HTML or Markdown, such as catalogs edited in a CMS, can be rendered
+//! with `YewI18n::t_html` and `YewI18n::t_markdown` instead of `Html::from_html_unchecked`.
+//! Both sanitize the message, keeping formatting elements and safe links only.
+//!
+//! ## Scopes
+//!
+//! [`I18nScope`] prefixes the keys of its descendants, and `use_scope` does the same for a
+//! single component. Keys starting with `/` stay absolute:
+//!
+//! ```rust,ignore
+//! html! {
+//!     <I18nScope prefix="checkout.summary">
+//!         // `i18n.t("total")` translates "checkout.summary.total" in here.
+//!         <Total />
+//!     </I18nScope>
+//! }
+//! ```
+//!
+//! ## Other Languages
+//!
+//! `YewI18n::t_in` and `YewI18n::t_in_with` translate a key in a given language without
+//! switching to it, and [`I18nLanguage`] renders its descendants in another language, as
+//! `use_translation_in` does for a single component:
+//!
+//! ```rust,ignore
+//! html! {
+//!     <a href="/es">{ i18n.t_in("es", "view_in_language") }</a>
+//!     <I18nLanguage language="es">
+//!         <LegalText />
+//!     </I18nLanguage>
+//! }
+//! ```
+//!
+//! ## Lazy Loading
+//!
+//! Instead of shipping every catalog up front, give `I18nProvider` a [`TranslationLoader`].
+//! A language missing from `translations` is loaded the first time it is selected, and
+//! `use_translation_suspense` suspends components until it is available. With the `http`
+//! feature, `TranslationLoader::http` fetches catalogs from a URL template:
+//!
+//! ```rust,ignore
+//! html! {
+//!     <I18nProvider
+//!         supported_languages={vec!["en", "fr", "de"]}
+//!         loader={TranslationLoader::http("/locales/{lang}.json")}
+//!     >
+//!         <Suspense fallback={html! { "Loading..." }}>
+//!             <MyComponent />
+//!         </Suspense>
+//!     </I18nProvider>
+//! }
+//! ```
+//!
+//! `YewI18n::is_loading` and `YewI18n::load_error` report the state of the current language
+//! for components that do not use suspense.
+//!
+//! ## Namespaces
+//!
+//! Large catalogs can be split into namespaces, stored as top-level objects of a language
+//! catalog and addressed with a `namespace:` prefix, as in `i18n.t("checkout:title")`. With a
+//! namespaced loader such as `TranslationLoader::http("/locales/{lang}/{ns}.json")`, the
+//! namespaces in the `namespaces` prop are loaded for every language, and components load
+//! their own on demand with `use_namespaces`:
+//!
+//! ```rust,ignore
+//! #[function_component]
+//! fn Checkout() -> HtmlResult {
+//!     let i18n = use_namespaces(&["checkout"])?;
+//!     Ok(html! { <h1>{ i18n.t("checkout:title") }</h1> })
+//! }
+//! ```
+//!
+//! ## Server-Side Rendering
+//!
+//! Enable the `ssr` feature on the server and the `hydration` feature on the client. Pass the
+//! language negotiated for the request through the `language` prop: the server loads its
+//! catalogs with the `loader` before rendering, and sends the language and loaded catalogs
+//! along with the HTML so the hydrating client renders the same language right away. The
+//! provider suspends meanwhile: render it inside a `Suspense`, or set its `suspense` prop.
+//!
+//! The [`negotiation`] module picks that language from the URL path, a locale cookie and the
+//! `Accept-Language` header. With the `axum` or `actix-web` feature enabled,
+//! `negotiation::RequestLocale` is a request extractor for those frameworks, and their
+//! `negotiate_locale` middleware negotiates the locale of every request.
+//!
+//! ## Localized Routes
+//!
+//! With the `router` feature enabled, the [`router`] module serves yew-router routes under a
+//! locale prefix such as `/fr/products`. `LocalizedSwitch` selects the language in the URL
+//! in the provider, `LocalizedLink` keeps the current locale in links, and
+//! `use_switch_language` moves the current page to another language. Route slugs can be
+//! translated through a catalog key given to the `slugs` prop, as in `/fr/produits`:
+//!
+//! ```rust,ignore
+//! html! {
+//!     <BrowserRouter>
+//!         <LocalizedLink<Route> to={Route::Products}>{ "Products" }</LocalizedLink<Route>>
+//!         <LocalizedSwitch<Route> render={switch} slugs={Some("routes")} />
+//!     </BrowserRouter>
+//! }
+//! ```
+//!
+//! ## Localized Head
+//!
+//! [`I18nHead`] sets the `lang` attribute of the document, translates its title and meta
+//! description from catalog keys, and emits `<link rel="alternate" hreflang>` entries for all
+//! supported languages, updating them whenever the language changes. With the `router`
+//! feature, `router::use_alternate_urls` provides the alternate URLs:
+//!
+//! ```rust,ignore
+//! html! {
+//!     <I18nHead
+//!         title="landing.title"
+//!         description="landing.description"
+//!         alternate_url={use_alternate_urls()}
+//!         base_url="https://example.com"
+//!     />
+//! }
+//! ```
+//!
+//! The head is only updated in the browser. Pages rendered on the server get the same tags
+//! from [`render_head`].
+//!
+//! ## Catalogs
+//!
+//! Translations map language codes to [`Catalog`]s, messages grouped into nested tables.
+//! With the default `json` feature, a `serde_json::Value` converts into a catalog with
+//! `.into()`. Without it, catalogs are built with `Catalog::from`, embedded with
+//! `include_translations!`, or read from [`binary`] catalogs, and yew-i18n does not parse
+//! JSON at runtime. The `http`, `ssr` and `hydration` features enable `json`.
+//!
+//! ```rust
+//! use yew_i18n::{Catalog, YewI18n};
+//! use std::collections::HashMap;
+//!
+//! let mut translations = HashMap::new();
+//! translations.insert("en".to_string(), Catalog::from([("nav", Catalog::from([("home", "Home")]))]));
+//!
+//! let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+//! assert_eq!(i18n.t("nav.home"), "Home");
+//! ```
+//!
+//! ## Binary Catalogs
+//!
+//! The [`binary`] module encodes catalogs into a compact format with deduplicated strings,
+//! read without parsing or copying by [`BinaryCatalog`](binary::BinaryCatalog). Produce it at
+//! build time with `cargo run --bin yew-i18n-catalog -- locales/ locales.bin` and pass it to
+//! the provider:
+//!
+//! ```rust,ignore
+//! let catalog = BinaryCatalog::new(include_bytes!("../locales.bin")).unwrap();
+//!
+//! html! {
+//!     <I18nProvider supported_languages={vec!["en", "fr"]} binary={catalog}>
+//!         <App />
+//!     </I18nProvider>
+//! }
+//! ```
+//!
+//! ## Translation Sources
+//!
+//! Implement [`TranslationSource`] to serve messages from another backend, such as Fluent,
+//! gettext or IndexedDB, and pass it to the provider through the `source` prop. Sources are
+//! looked up after the `translations` prop, and can load missing languages asynchronously.
+//!
+//! ```rust,ignore
+//! let source: Arc<dyn TranslationSource> = Arc::new(FluentSource::new(bundles));
+//!
+//! html! {
+//!     <I18nProvider supported_languages={vec!["en", "fr"]} source={source}>
+//!         <App />
+//!     </I18nProvider>
+//! }
+//! ```
+//!
+//! ## Without Yew
+//!
+//! Catalogs, lookup, message formatting and [`negotiation`] do not depend on Yew: the
+//! provider, the hooks and the components are the `yew` feature, enabled by default. With
+//! `default-features = false`, `YewI18n` translates in plain Rust, on servers, in CLIs and in
+//! tests, with the same catalogs as the app. `YewI18n` is then `Send` and `Sync`, so a server
+//! can share one instance between its threads. The `ssr`, `hydration` and `router` features
+//! enable `yew`.
+//!
+//! ```rust
+//! use yew_i18n::{Catalog, YewI18n};
+//! use std::collections::HashMap;
+//!
+//! let mut translations = HashMap::new();
+//! translations.insert("fr".to_string(), Catalog::from([("subject", "Bienvenue, {name} !")]));
+//!
+//! let mut i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+//! i18n.set_translation_language("fr").unwrap();
+//! assert_eq!(i18n.t_with("subject", &[("name", "Ada")]), "Bienvenue, Ada !");
+//!
+//! # #[cfg(not(feature = "yew"))]
+//! # {
+//! fn assert_send_sync<T: Send + Sync>(_: &T) {}
+//! assert_send_sync(&i18n);
+//! # }
+//! ```
+//!
+//! ## XLIFF
+//!
+//! With the `xliff` feature enabled, the [`xliff`] module reads and writes XLIFF 1.2 and 2.0
+//! documents, so catalogs can round-trip through professional translation tools.
+//!
 //! ## Contribution
 //!
 //! If you encounter any issues or have suggestions for improvements, feel free to contribute
@@ -111,46 +374,122 @@
 //! Special thanks to the Yew community and contributors for such an amazing framework.
 //!
 
-use serde_json::Value;
+pub mod binary;
+mod catalog;
+#[cfg(feature = "yew")]
+mod head;
+#[cfg(feature = "yew")]
+mod hydration;
+#[cfg(feature = "yew")]
+mod language;
+mod loader;
+#[cfg(feature = "yew")]
+mod markup;
+mod message;
+pub mod negotiation;
+#[cfg(feature = "yew")]
+mod provider;
+#[cfg(feature = "router")]
+pub mod router;
+#[cfg(feature = "yew")]
+mod scope;
+mod source;
+#[cfg(feature = "yew")]
+mod trans;
+#[cfg(feature = "xliff")]
+pub mod xliff;
+
+pub use catalog::Catalog;
+#[cfg(feature = "yew")]
+pub use head::{render_head, I18nHead, I18nHeadProps};
+#[cfg(feature = "yew")]
+pub use language::{use_translation_in, I18nLanguage, I18nLanguageProps};
+pub use loader::TranslationLoader;
+#[cfg(feature = "yew")]
+pub use provider::{
+    use_namespaces, use_translation, use_translation_suspense, use_try_translation, I18nProvider,
+    Translations, YewI18nProviderConfig,
+};
+#[cfg(feature = "yew")]
+pub use scope::{use_scope, I18nScope, I18nScopeProps};
+pub use source::{CatalogFuture, TranslationSource};
+#[cfg(feature = "yew")]
+pub use trans::{Trans, TransProps};
+
+#[cfg(feature = "macros")]
+pub use yew_i18n_macros::{include_translations, messages, t};
+
+use binary::BinaryCatalog;
+use catalog::{CatalogSet, Entry};
+#[cfg(feature = "yew")]
+use loader::{CatalogId, LoaderHandle};
+use std::borrow::Cow;
 use std::collections::HashMap;
+#[cfg(feature = "yew")]
+use std::collections::HashSet;
+#[cfg(feature = "yew")]
+use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(feature = "yew")]
 use yew::prelude::*;
+#[cfg(feature = "yew")]
+use yew::suspense::SuspensionResult;
 
 /// Configuration for the YewI18n module, specifying supported languages and translations.
 #[derive(Debug, Clone, PartialEq)]
 pub struct YewI18nConfig {
     /// List of supported languages in the application.
     pub supported_languages: Vec<&'static str>,
-    /// Translations for different languages, represented as a mapping from language codes to JSON values.
-    pub translations: HashMap<String, serde_json::Value>,
-}
-
-/// Configuration for the YewI18nProvider component.
-#[derive(Debug, Clone, PartialEq, Properties)]
-pub struct YewI18nProviderConfig {
-    /// List of supported languages. Defaults to English and French if not specified.
-    #[prop_or_else(|| vec!["en", "fr"])]
-    pub supported_languages: Vec<&'static str>,
-    /// Translations for different languages, represented as a mapping from language codes to JSON values.
-    #[prop_or_default]
-    pub translations: HashMap<String, serde_json::Value>,
-    /// The child components to be wrapped with the YewI18n context.
-    pub children: Html,
+    /// Translations for different languages, represented as a mapping from language codes to catalogs.
+    ///
+    /// Ignored by [`YewI18n::new`], which takes the catalogs as a separate argument.
+    #[deprecated(
+        note = "ignored by `YewI18n::new`; pass the catalogs once to `YewI18n::from_translations`"
+    )]
+    pub translations: HashMap<String, Catalog>,
 }
 
 /// The YewI18n struct representing the state and methods for internationalization.
-#[derive(Clone, PartialEq)]
+///
+/// Catalogs are shared between clones, so cloning and comparing instances does not depend on
+/// the size of the catalogs. Without the `yew` feature, instances are `Send` and `Sync`, and
+/// can be shared between the threads of a server.
+#[derive(Clone)]
 pub struct YewI18n {
-    /// Configuration for YewI18n, specifying supported languages and translations.
-    pub config: YewI18nConfig,
+    /// The supported language codes, the default one first.
+    supported_languages: Arc<Vec<&'static str>>,
     /// The current language code for translations.
     current_language: String,
-    /// Translations for different languages, represented as a mapping from language codes to JSON values.
-    translations: HashMap<String, serde_json::Value>,
+    /// Translations for different languages, flattened on first use.
+    translations: Arc<CatalogSet>,
+    /// Handle used to request missing catalogs from the provider, if it has a loader.
+    #[cfg(feature = "yew")]
+    loader: Option<LoaderHandle>,
+    /// Catalogs being loaded.
+    #[cfg(feature = "yew")]
+    loading: Rc<HashSet<CatalogId>>,
+    /// Errors raised while loading catalogs.
+    #[cfg(feature = "yew")]
+    load_errors: Rc<HashMap<CatalogId, String>>,
+    /// Callback switching the language of the provider, if any.
+    #[cfg(feature = "yew")]
+    selector: Option<Callback<String>>,
+    /// Namespaces required by the component holding this instance.
+    namespaces: Vec<String>,
+    /// Prefix prepended to relative keys, set by `I18nScope`.
+    scope: Option<String>,
+    /// The instance of the enclosing provider, looked up for keys missing from this one.
+    #[cfg(feature = "yew")]
+    parent: Option<Rc<YewI18n>>,
 }
 
 impl YewI18n {
     /// Creates a new instance of YewI18n.
     ///
+    /// Only the supported languages of `config` are used: the catalogs are read from
+    /// `translations`. Prefer [`from_translations`](Self::from_translations), which does not
+    /// need a second copy of the catalogs.
+    ///
     /// # Arguments
     ///
     /// * `config` - Configuration for YewI18n.
@@ -167,30 +506,152 @@ impl YewI18n {
     /// use std::collections::HashMap;
     ///
     /// let supported_languages = vec!["en", "fr"];
-    /// let translations = HashMap::new();
     ///
-    /// let result = YewI18n::new(YewI18nConfig { supported_languages, translations: translations.clone()}, translations);
+    /// #[allow(deprecated)]
+    /// let config = YewI18nConfig { supported_languages, translations: HashMap::new() };
+    /// let result = YewI18n::new(config, HashMap::new());
     /// assert!(result.is_ok());
     /// ```
     pub fn new(
         config: YewI18nConfig,
-        translations: HashMap<String, serde_json::Value>,
+        translations: HashMap<String, Catalog>,
+    ) -> Result<Self, String> {
+        Self::from_translations(config.supported_languages, translations)
+    }
+
+    /// Creates a new instance of YewI18n from the supported languages and their catalogs.
+    ///
+    /// The catalogs are taken as they are, without copying them: pass a `HashMap`, or an
+    /// `Arc` shared with other instances, such as the one returned by `include_translations!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `supported_languages` - The supported language codes, the default one first.
+    /// * `translations` - Translations for different languages.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the initialized YewI18n instance or an error message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+    ///
+    /// let i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+    /// assert_eq!(i18n.t("greeting"), "Hello");
+    /// ```
+    pub fn from_translations(
+        supported_languages: Vec<&'static str>,
+        translations: impl Into<Arc<HashMap<String, Catalog>>>,
     ) -> Result<Self, String> {
-        let current_language = config
-            .supported_languages
-            .get(0)
+        let translations = CatalogSet::new(translations.into(), Vec::new());
+        Self::with_catalogs(Arc::new(supported_languages), Arc::new(translations))
+    }
+
+    /// Creates a new instance of YewI18n reading its messages from a binary catalog.
+    ///
+    /// # Arguments
+    ///
+    /// * `supported_languages` - The supported language codes, the default one first.
+    /// * `catalog` - Catalogs produced by `yew-i18n-catalog` or [`binary::encode`].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the initialized YewI18n instance or an error message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::binary::{self, BinaryCatalog};
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("welcome", "Welcome back, {name}!")]));
+    /// // Binary catalogs are usually embedded with `include_bytes!`.
+    /// let bytes: &'static [u8] = binary::encode(&translations).leak();
+    ///
+    /// let i18n = YewI18n::from_binary(vec!["en"], BinaryCatalog::new(bytes).unwrap()).unwrap();
+    /// assert_eq!(i18n.t_with("welcome", &[("name", "Ada")]), "Welcome back, Ada!");
+    /// ```
+    pub fn from_binary(
+        supported_languages: Vec<&'static str>,
+        catalog: BinaryCatalog<'static>,
+    ) -> Result<Self, String> {
+        Self::from_source(supported_languages, Arc::new(catalog))
+    }
+
+    /// Creates a new instance of YewI18n reading its messages from a translation backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `supported_languages` - The supported language codes, the default one first.
+    /// * `source` - The backend serving the messages.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the initialized YewI18n instance or an error message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    /// use std::sync::Arc;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+    ///
+    /// let i18n = YewI18n::from_source(vec!["en"], Arc::new(translations)).unwrap();
+    /// assert_eq!(i18n.t("greeting"), "Hello");
+    /// ```
+    pub fn from_source(
+        supported_languages: Vec<&'static str>,
+        source: Arc<dyn TranslationSource>,
+    ) -> Result<Self, String> {
+        let translations = CatalogSet::new(Arc::default(), vec![source]);
+        Self::with_catalogs(Arc::new(supported_languages), Arc::new(translations))
+    }
+
+    /// Creates an instance sharing the given catalogs.
+    fn with_catalogs(
+        supported_languages: Arc<Vec<&'static str>>,
+        translations: Arc<CatalogSet>,
+    ) -> Result<Self, String> {
+        let current_language = supported_languages
+            .first()
             .cloned()
             .ok_or_else(|| "You must add at least one supported language".to_string())?;
 
         Ok(YewI18n {
-            config,
+            supported_languages,
             current_language: current_language.to_string(),
             translations,
+            #[cfg(feature = "yew")]
+            loader: None,
+            #[cfg(feature = "yew")]
+            loading: Rc::default(),
+            #[cfg(feature = "yew")]
+            load_errors: Rc::default(),
+            #[cfg(feature = "yew")]
+            selector: None,
+            namespaces: Vec::new(),
+            scope: None,
+            #[cfg(feature = "yew")]
+            parent: None,
         })
     }
 
     /// Sets the current language for translations.
     ///
+    /// Inside an `I18nProvider` with a `loader`, selecting a language whose catalog is not
+    /// loaded yet starts loading it.
+    ///
     /// # Arguments
     ///
     /// * `language` - The language code to set.
@@ -202,89 +663,543 @@ impl YewI18n {
     /// # Examples
     ///
     /// ```
-    /// use yew_i18n::{YewI18n, YewI18nConfig};
+    /// use yew_i18n::YewI18n;
     /// use std::collections::HashMap;
     ///
     /// let supported_languages = vec!["en", "fr"];
     /// let translations = HashMap::new();
     ///
-    /// let mut i18n = YewI18n::new(YewI18nConfig { supported_languages, translations: translations.clone()}, translations).unwrap();
+    /// let mut i18n = YewI18n::from_translations(supported_languages, translations).unwrap();
     /// assert!(i18n.set_translation_language("fr").is_ok());
     /// ```
     pub fn set_translation_language(&mut self, language: &str) -> Result<(), String> {
-        if self.config.supported_languages.contains(&&language) {
+        if self.supported_languages.contains(&language) {
             self.current_language = language.to_string();
+            #[cfg(feature = "yew")]
+            self.request_load();
             Ok(())
         } else {
             Err(format!("Language '{}' is not supported", language))
         }
     }
 
+    /// Switches the language of the enclosing `I18nProvider`, and so of every component
+    /// using its context.
+    ///
+    /// [`set_translation_language`](Self::set_translation_language) only changes this
+    /// instance, which is a copy of the context. This also renders the provider again in the
+    /// new language. A nested provider forwards the language to the enclosing one, whose
+    /// language it follows. Outside of a provider, only this instance is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language code to set.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error message if the language is not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "yew")]
+    /// # {
+    /// use yew::prelude::*;
+    /// use yew_i18n::use_translation;
+    ///
+    /// #[function_component]
+    /// fn LanguageSwitcher() -> Html {
+    ///     let i18n = use_translation();
+    ///     let onclick = Callback::from(move |_| {
+    ///         let _ = i18n.clone().select_language("fr");
+    ///     });
+    ///     html! { <button {onclick}>{ "Français" }</button> }
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "yew")]
+    pub fn select_language(&mut self, language: &str) -> Result<(), String> {
+        self.set_translation_language(language)?;
+        if let Some(selector) = &self.selector {
+            selector.emit(language.to_string());
+        }
+        Ok(())
+    }
+
     /// Retrieves a translated string for a given key.
     ///
+    /// Keys containing `.` that are not found as-is are looked up as paths into nested
+    /// objects, so `"nav.home"` resolves `{ "nav": { "home": "Home" } }`. A `namespace:`
+    /// prefix looks the rest of the key up in the namespace object of the catalog, so
+    /// `"checkout:title"` resolves `{ "checkout": { "title": "Checkout" } }`.
+    ///
     /// # Arguments
     ///
     /// * `key` - The translation key.
     ///
     /// # Returns
     ///
-    /// The translated string or an error message if the key is not found.
+    /// The translated string, borrowed from the catalog without allocating, or an error message
+    /// if the key is not found.
     ///
     /// # Examples
     ///
     /// ```
-    /// use yew_i18n::{YewI18n, YewI18nConfig};
+    /// use yew_i18n::{Catalog, YewI18n};
     /// use std::collections::HashMap;
-    /// use serde_json::json;
     ///
     /// let supported_languages = vec!["en", "fr"];
     /// let mut translations = HashMap::new();
-    /// translations.insert("en".to_string(), json!({ "greeting": "Hello" }));
-    /// translations.insert("fr".to_string(), json!({ "greeting": "Bonjour" }));
+    /// translations.insert("en".to_string(), Catalog::from([
+    ///     ("greeting", Catalog::from("Hello")),
+    ///     ("nav", Catalog::from([("home", "Home")])),
+    /// ]));
+    /// translations.insert("fr".to_string(), Catalog::from([
+    ///     ("greeting", Catalog::from("Bonjour")),
+    ///     ("nav", Catalog::from([("home", "Accueil")])),
+    /// ]));
     ///
-    /// let i18n = YewI18n::new(YewI18nConfig { supported_languages, translations: translations.clone()}, translations).unwrap();
+    /// let i18n = YewI18n::from_translations(supported_languages, translations).unwrap();
     /// assert_eq!(i18n.t("greeting"), "Hello");
+    /// assert_eq!(i18n.t("nav.home"), "Home");
+    /// assert_eq!(i18n.t("nav:home"), "Home");
     /// ```
-    pub fn t(&self, key: &str) -> String {
-        self.translations
-            .get(&self.current_language)
-            .and_then(|language_json| language_json.get(key))
-            .map_or_else(
-                || {
-                    Err(format!(
-                        "Unable to find the key '{}' in the language '{}'",
-                        key, self.current_language
-                    ))
-                },
-                |value| match value {
-                    Value::String(s) => Ok(s.clone()),
-                    _ => Ok(value.to_string()),
-                },
-            )
-            .unwrap_or_else(|err| err)
+    ///
+    /// Escaped braces are unescaped, the same way as by [`t_with`](Self::t_with):
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("braces", "Write {{name}}")]));
+    ///
+    /// let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+    /// assert_eq!(i18n.t("braces"), "Write {name}");
+    /// assert_eq!(i18n.t("braces"), i18n.t_with("braces", &[]));
+    /// ```
+    pub fn t(&self, key: &str) -> Cow<'_, str> {
+        self.t_in(&self.current_language, key)
+    }
+
+    /// Retrieves a translated string for a given key and fills in its `{name}` placeholders.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The translation key.
+    /// * `args` - The placeholder names and their values.
+    ///
+    /// # Returns
+    ///
+    /// The formatted string, borrowed from the catalog when the message has no placeholders, or
+    /// an error message if the key is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let supported_languages = vec!["en"];
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("welcome", "Welcome back, {name}!")]));
+    ///
+    /// let i18n = YewI18n::from_translations(supported_languages, translations).unwrap();
+    /// assert_eq!(i18n.t_with("welcome", &[("name", "Ada")]), "Welcome back, Ada!");
+    /// ```
+    pub fn t_with(&self, key: &str, args: &[(&str, &str)]) -> Cow<'_, str> {
+        self.t_in_with(&self.current_language, key, args)
+    }
+
+    /// Retrieves a translated string for a given key in a language other than the current
+    /// one, without switching to it.
+    ///
+    /// The language is looked up as is, whether supported or not, and its catalogs are not
+    /// loaded on demand: wrap the components using it in an `I18nLanguage` to load them.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language code to translate into.
+    /// * `key` - The translation key.
+    ///
+    /// # Returns
+    ///
+    /// The translated string, or an error message if the key is not found in the language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([("view_in", "View this page in English")]));
+    /// translations.insert("es".to_string(), Catalog::from([("view_in", "Ver esta página en español")]));
+    ///
+    /// let i18n = YewI18n::from_translations(vec!["en", "es"], translations).unwrap();
+    /// assert_eq!(i18n.t_in("es", "view_in"), "Ver esta página en español");
+    /// assert_eq!(i18n.current_language(), "en");
+    /// ```
+    pub fn t_in(&self, language: &str, key: &str) -> Cow<'_, str> {
+        self.t_in_with(language, key, &[])
+    }
+
+    /// Retrieves a translated string for a given key in a language other than the current
+    /// one and fills in its `{name}` placeholders, see [`t_in`](Self::t_in).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("fr".to_string(), Catalog::from([("welcome", "Bienvenue, {name} !")]));
+    ///
+    /// let i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+    /// assert_eq!(i18n.t_in_with("fr", "welcome", &[("name", "Ada")]), "Bienvenue, Ada !");
+    /// ```
+    pub fn t_in_with(&self, language: &str, key: &str, args: &[(&str, &str)]) -> Cow<'_, str> {
+        match self.resolve(language, key) {
+            Ok(entry) => entry.format(args),
+            Err(err) => Cow::Owned(err),
+        }
+    }
+
+    /// Renders a translation containing HTML into sanitized `Html`.
+    ///
+    /// Only a whitelist of formatting elements is kept (`a`, `b`, `strong`, `em`, `code`,
+    /// `br`, `p`, lists, ...), links keep their `href` only when it is relative or uses the
+    /// `http`, `https`, `mailto` or `tel` scheme, and `script` and `style` elements are dropped
+    /// along with their content. Catalogs from untrusted sources can be rendered safely.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The translation key.
+    /// * `args` - The placeholder names and their values, always rendered as text.
+    ///
+    /// # Returns
+    ///
+    /// The sanitized translation, or an error message if the key is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # #[cfg(feature = "ssr")]
+    /// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+    /// use yew::prelude::*;
+    /// use yew_i18n::YewI18n;
+    /// use std::collections::HashMap;
+    /// use serde_json::json;
+    ///
+    /// #[function_component]
+    /// fn Notice() -> Html {
+    ///     let mut translations = HashMap::new();
+    ///     translations.insert(
+    ///         "en".to_string(),
+    ///         json!({ "notice": "<b>Hi {name}</b><script>alert(1)</script> <a href=\"javascript:alert(1)\" onclick=\"x\">here</a>" }).into(),
+    ///     );
+    ///     let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+    ///     i18n.t_html_with("notice", &[("name", "<i>Ada</i>")])
+    /// }
+    ///
+    /// let html = yew::LocalServerRenderer::<Notice>::new().hydratable(false).render().await;
+    /// assert_eq!(html, "<b>Hi &lt;i&gt;Ada&lt;/i&gt;</b> <a>here</a>");
+    /// # }));
+    /// ```
+    #[cfg(feature = "yew")]
+    pub fn t_html_with(&self, key: &str, args: &[(&str, &str)]) -> Html {
+        match self.lookup(&self.current_language, key) {
+            Ok(message) => markup::html(&message, args),
+            Err(err) => Html::from(err),
+        }
+    }
+
+    /// Renders a translation containing HTML into sanitized `Html`, see
+    /// [`t_html_with`](Self::t_html_with).
+    #[cfg(feature = "yew")]
+    pub fn t_html(&self, key: &str) -> Html {
+        self.t_html_with(key, &[])
+    }
+
+    /// Renders a translation written in inline Markdown into `Html`.
+    ///
+    /// Supports `**strong**`, `*emphasis*`, `` `code` ``, `[links](url)` and line breaks, with
+    /// the same link sanitization as [`t_html_with`](Self::t_html_with). Raw HTML in the
+    /// message is rendered as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # #[cfg(feature = "ssr")]
+    /// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+    /// use yew::prelude::*;
+    /// use yew_i18n::YewI18n;
+    /// use std::collections::HashMap;
+    /// use serde_json::json;
+    ///
+    /// #[function_component]
+    /// fn Notice() -> Html {
+    ///     let mut translations = HashMap::new();
+    ///     translations.insert("en".to_string(), json!({ "notice": "Read the **[terms](/terms)** first" }).into());
+    ///     let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+    ///     i18n.t_markdown("notice")
+    /// }
+    ///
+    /// let html = yew::LocalServerRenderer::<Notice>::new().hydratable(false).render().await;
+    /// assert_eq!(html, r#"Read the <strong><a href="/terms">terms</a></strong> first"#);
+    /// # }));
+    /// ```
+    ///
+    /// As in CommonMark, `_` only delimits emphasis at word boundaries:
+    ///
+    /// ```
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # #[cfg(feature = "ssr")]
+    /// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+    /// use yew::prelude::*;
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// #[function_component]
+    /// fn Hint() -> Html {
+    ///     let mut translations = HashMap::new();
+    ///     translations.insert("en".to_string(), Catalog::from([("hint", "Set snake_case_name to _on_")]));
+    ///     let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+    ///     i18n.t_markdown("hint")
+    /// }
+    ///
+    /// let html = yew::LocalServerRenderer::<Hint>::new().hydratable(false).render().await;
+    /// assert_eq!(html, "Set snake_case_name to <em>on</em>");
+    /// # }));
+    /// ```
+    #[cfg(feature = "yew")]
+    pub fn t_markdown_with(&self, key: &str, args: &[(&str, &str)]) -> Html {
+        match self.lookup(&self.current_language, key) {
+            Ok(message) => markup::markdown(&message, args),
+            Err(err) => Html::from(err),
+        }
     }
-}
 
-/// Yew component for providing the YewI18n context to its children.
-#[function_component(I18nProvider)]
-pub fn i18n_provider(props: &YewI18nProviderConfig) -> Html {
-    let i18n = YewI18n::new(
+    /// Renders a translation written in inline Markdown into `Html`, see
+    /// [`t_markdown_with`](Self::t_markdown_with).
+    #[cfg(feature = "yew")]
+    pub fn t_markdown(&self, key: &str) -> Html {
+        self.t_markdown_with(key, &[])
+    }
+
+    /// Returns a copy of the supported languages and in-memory catalogs of this instance.
+    ///
+    /// Instances used to hold them in a public `config` field. The catalogs are now shared
+    /// between instances, so this copies them, and leaves out the messages of sources,
+    /// binary catalogs and enclosing providers.
+    #[deprecated(note = "use `supported_languages`, and `t` or `t_in` to read messages")]
+    pub fn config(&self) -> YewI18nConfig {
+        #[allow(deprecated)]
         YewI18nConfig {
-            supported_languages: props.supported_languages.clone(),
-            translations: props.translations.clone(),
-        },
-        props.translations.clone(),
-    )
-    .expect("Failed to initialize YewI18n");
+            supported_languages: self.supported_languages.to_vec(),
+            translations: self.translations.translations().clone(),
+        }
+    }
+
+    /// Returns the supported language codes, the default one first.
+    pub fn supported_languages(&self) -> &[&'static str] {
+        &self.supported_languages
+    }
+
+    /// Returns the current language code.
+    pub fn current_language(&self) -> &str {
+        &self.current_language
+    }
 
-    let ctx = use_state(|| i18n);
+    /// Returns whether the catalogs of the current language are still being loaded.
+    #[cfg(feature = "yew")]
+    pub fn is_loading(&self) -> bool {
+        self.loader.is_some()
+            && self
+                .required_catalogs()
+                .iter()
+                .any(|id| !self.has_catalog(id) && !self.load_errors.contains_key(id))
+    }
 
-    html! {
-        <ContextProvider<YewI18n> context={(*ctx).clone()}>{ props.children.clone() }</ContextProvider<YewI18n>>
+    /// Returns the error raised while loading a catalog of the current language, if any.
+    #[cfg(feature = "yew")]
+    pub fn load_error(&self) -> Option<&str> {
+        self.required_catalogs()
+            .iter()
+            .find_map(|id| self.load_errors.get(id))
+            .map(String::as_str)
+    }
+
+    /// Returns the catalogs the current language needs: one per namespace for namespaced
+    /// loaders, the whole language otherwise.
+    #[cfg(feature = "yew")]
+    fn required_catalogs(&self) -> Vec<CatalogId> {
+        match &self.loader {
+            Some(loader) if loader.namespaced => self
+                .namespaces
+                .iter()
+                .map(|namespace| (self.current_language.clone(), Some(namespace.clone())))
+                .collect(),
+            _ => vec![(self.current_language.clone(), None)],
+        }
+    }
+
+    #[cfg(feature = "yew")]
+    fn has_catalog(&self, (language, namespace): &CatalogId) -> bool {
+        match namespace {
+            None => self.translations.contains(language),
+            Some(namespace) => self.translations.contains_namespace(language, namespace),
+        }
+    }
+
+    #[cfg(feature = "yew")]
+    fn add_namespaces(&mut self, namespaces: &[&str]) {
+        for namespace in namespaces {
+            if !self.namespaces.iter().any(|known| known == namespace) {
+                self.namespaces.push(namespace.to_string());
+            }
+        }
+    }
+
+    #[cfg(feature = "yew")]
+    fn request_load(&self) {
+        if let Some(loader) = &self.loader {
+            for id in self.required_catalogs() {
+                if !self.has_catalog(&id) && !self.loading.contains(&id) {
+                    loader.request.emit(id);
+                }
+            }
+        }
+    }
+
+    /// Suspends while catalogs are being loaded, if the provider has a loader.
+    #[cfg(feature = "yew")]
+    fn suspend_while_loading(self) -> SuspensionResult<Self> {
+        match &self.loader {
+            Some(loader) if !self.loading.is_empty() || self.is_loading() => {
+                self.request_load();
+                Err(loader.suspend())
+            }
+            _ => Ok(self),
+        }
+    }
+
+    /// Returns a copy of this instance resolving relative keys under a prefix.
+    ///
+    /// Keys are prefixed with `prefix` and a `.`, so `t("total")` on an instance scoped to
+    /// `checkout.summary` looks up `checkout.summary.total`. A prefix starting with `/`
+    /// replaces the current scope, any other is appended to it. Keys starting with `/` are
+    /// absolute and ignore the scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, YewI18n};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert("en".to_string(), Catalog::from([
+    ///     ("checkout", Catalog::from([("summary", Catalog::from([("total", "Total")]))])),
+    ///     ("common", Catalog::from([("ok", "OK")])),
+    /// ]));
+    ///
+    /// let i18n = YewI18n::from_translations(vec!["en"], translations).unwrap();
+    ///
+    /// let summary = i18n.scoped("checkout").scoped("summary");
+    /// assert_eq!(summary.t("total"), "Total");
+    /// assert_eq!(summary.t("/common.ok"), "OK");
+    /// ```
+    pub fn scoped(&self, prefix: &str) -> YewI18n {
+        let mut i18n = self.clone();
+        i18n.scope = match (prefix.strip_prefix('/'), &self.scope) {
+            (Some(prefix), _) => Some(prefix.to_string()),
+            (None, _) if prefix.is_empty() => self.scope.clone(),
+            (None, Some(scope)) => Some(format!("{}.{}", scope, prefix)),
+            (None, None) => Some(prefix.to_string()),
+        }
+        .filter(|scope| !scope.is_empty());
+        i18n
+    }
+
+    /// Returns the message of a key as written in the catalog, with its placeholders and
+    /// escaped braces, for renderers filling them in themselves.
+    fn lookup(&self, language: &str, key: &str) -> Result<Cow<'_, str>, String> {
+        self.resolve(language, key).map(|entry| match entry {
+            Entry::Message(message) => Cow::Borrowed(message.source()),
+            Entry::Text(message) => message,
+        })
+    }
+
+    fn resolve(&self, language: &str, key: &str) -> Result<Entry<'_>, String> {
+        let key = match (key.strip_prefix('/'), &self.scope) {
+            (Some(key), _) => Cow::Borrowed(key),
+            (None, Some(scope)) => Cow::Owned(format!("{}.{}", scope, key)),
+            (None, None) => Cow::Borrowed(key),
+        };
+        self.find_message(language, &key).ok_or_else(|| {
+            format!(
+                "Unable to find the key '{}' in the language '{}'",
+                key, language
+            )
+        })
+    }
+
+    /// Finds the message of a key in a language, falling back to the enclosing providers.
+    fn find_message(&self, language: &str, key: &str) -> Option<Entry<'_>> {
+        let catalogs = &self.translations;
+        let entry = catalogs
+            .entry(language, key)
+            .or_else(|| {
+                let (namespace, key) = key.split_once(':')?;
+                catalogs.entry(language, &format!("{}.{}", namespace, key))
+            })
+            .or_else(|| {
+                self.namespaces.iter().find_map(|namespace| {
+                    catalogs.entry(language, &format!("{}.{}", namespace, key))
+                })
+            });
+        #[cfg(feature = "yew")]
+        let entry = entry.or_else(|| self.parent.as_ref()?.find_message(language, key));
+        entry
+    }
+
+    /// Returns the keys under a table of a language whose message is `message`, without the
+    /// table, including those of the enclosing providers.
+    #[cfg(feature = "router")]
+    fn keys_with_message(&self, language: &str, table: &str, message: &str) -> Vec<String> {
+        let mut keys = self
+            .translations
+            .reverse(language, table)
+            .get(message)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(parent) = &self.parent {
+            keys.extend(parent.keys_with_message(language, table, message));
+        }
+        keys
     }
 }
 
-#[hook]
-pub fn use_translation() -> YewI18n {
-    use_context::<YewI18n>().expect("No I18n context provided")
+impl PartialEq for YewI18n {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "yew")]
+        if self.loader != other.loader
+            || !Rc::ptr_eq(&self.loading, &other.loading)
+            || !Rc::ptr_eq(&self.load_errors, &other.load_errors)
+            || self.selector != other.selector
+            || self.parent != other.parent
+        {
+            return false;
+        }
+        self.supported_languages == other.supported_languages
+            // Catalogs are compared by identity: they are shared, and replaced rather than
+            // modified.
+            && Arc::ptr_eq(&self.translations, &other.translations)
+            && self.current_language == other.current_language
+            && self.namespaces == other.namespaces
+            && self.scope == other.scope
+    }
 }
@@ -0,0 +1,383 @@
+//! Lazy loading of language catalogs.
+//!
+//! A [`TranslationLoader`] fetches the catalog of a language the first time it is selected,
+//! so only the languages a user actually reads are downloaded. Namespaced loaders fetch one
+//! namespace of a language at a time, stored under the namespace key of the language catalog.
+
+use crate::{Catalog, CatalogFuture, TranslationSource};
+#[cfg(feature = "yew")]
+use std::cell::RefCell;
+#[cfg(feature = "yew")]
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(feature = "yew")]
+use yew::prelude::*;
+#[cfg(feature = "yew")]
+use yew::suspense::{Suspension, SuspensionHandle};
+
+/// Identifies a loadable catalog: a language, and a namespace for namespaced loaders.
+#[cfg(feature = "yew")]
+pub(crate) type CatalogId = (String, Option<String>);
+
+/// Loads the catalog of a language on demand.
+///
+/// Pass it to `I18nProvider` through the `loader` prop. Catalogs already present in the
+/// `translations` prop are never fetched.
+#[derive(Clone)]
+pub struct TranslationLoader {
+    load: Rc<dyn Fn(String, Option<String>) -> CatalogFuture>,
+    namespaced: bool,
+    /// Whether the loader can run outside of a browser, during server-side rendering.
+    server: bool,
+}
+
+impl TranslationLoader {
+    /// Creates a loader from an async function returning the catalog of a language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, TranslationLoader};
+    ///
+    /// let loader = TranslationLoader::new(|language: String| async move {
+    ///     match language.as_str() {
+    ///         "fr" => Ok(Catalog::from([("greeting", "Bonjour")])),
+    ///         _ => Err(format!("No catalog for '{}'", language)),
+    ///     }
+    /// });
+    /// ```
+    pub fn new<F, Fut>(load: F) -> Self
+    where
+        F: Fn(String) -> Fut + 'static,
+        Fut: Future<Output = Result<Catalog, String>> + 'static,
+    {
+        TranslationLoader {
+            load: Rc::new(move |language, _| Box::pin(load(language))),
+            namespaced: false,
+            server: true,
+        }
+    }
+
+    /// Creates a loader from an async function returning one namespace of a language.
+    ///
+    /// Namespaces are loaded when a component asks for them with `use_namespaces`, or for
+    /// every language when listed in the `namespaces` prop of `I18nProvider`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::{Catalog, TranslationLoader};
+    ///
+    /// let loader = TranslationLoader::with_namespaces(|language: String, namespace: String| async move {
+    ///     match (language.as_str(), namespace.as_str()) {
+    ///         ("en", "checkout") => Ok(Catalog::from([("title", "Checkout")])),
+    ///         _ => Err(format!("No catalog for '{}:{}'", language, namespace)),
+    ///     }
+    /// });
+    /// ```
+    pub fn with_namespaces<F, Fut>(load: F) -> Self
+    where
+        F: Fn(String, String) -> Fut + 'static,
+        Fut: Future<Output = Result<Catalog, String>> + 'static,
+    {
+        TranslationLoader {
+            load: Rc::new(move |language, namespace| {
+                Box::pin(load(language, namespace.unwrap_or_default()))
+            }),
+            namespaced: true,
+            server: true,
+        }
+    }
+
+    /// Creates a loader fetching catalogs over HTTP.
+    ///
+    /// Every `{lang}` in the URL template is replaced by the language code, and the response
+    /// body must be the JSON catalog of that language. A template containing `{ns}` makes a
+    /// namespaced loader, fetching one file per namespace.
+    ///
+    /// The requests are made with the browser's `fetch`, so the loader does not run during
+    /// server-side rendering: the server renders with the `translations` and `source` props
+    /// only, and the client loads the missing catalogs once hydrated. To render a page
+    /// with loaded catalogs on the server, pass it a loader created with
+    /// [`TranslationLoader::new`] that reads them from disk, or a `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::TranslationLoader;
+    ///
+    /// let loader = TranslationLoader::http("/locales/{lang}.json");
+    /// let namespaced = TranslationLoader::http("/locales/{lang}/{ns}.json");
+    /// ```
+    ///
+    /// On the server, the page renders in the requested language without the missing
+    /// catalog:
+    ///
+    /// ```
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # #[cfg(feature = "ssr")]
+    /// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+    /// use std::collections::HashMap;
+    /// use yew::prelude::*;
+    /// use yew_i18n::{use_translation_suspense, Catalog, I18nProvider, TranslationLoader};
+    ///
+    /// #[function_component]
+    /// fn Greeting() -> HtmlResult {
+    ///     let i18n = use_translation_suspense()?;
+    ///     Ok(html! { <p lang={i18n.current_language().to_string()}>{ i18n.t_in("en", "greeting") }</p> })
+    /// }
+    ///
+    /// #[function_component]
+    /// fn App() -> Html {
+    ///     let mut translations = HashMap::new();
+    ///     translations.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+    ///     let loader = use_memo((), |_| TranslationLoader::http("/locales/{lang}.json"));
+    ///
+    ///     html! {
+    ///         <Suspense>
+    ///             <I18nProvider
+    ///                 supported_languages={vec!["en", "fr"]}
+    ///                 translations={translations}
+    ///                 language="fr"
+    ///                 loader={(*loader).clone()}
+    ///             >
+    ///                 <Greeting />
+    ///             </I18nProvider>
+    ///         </Suspense>
+    ///     }
+    /// }
+    ///
+    /// let html = yew::LocalServerRenderer::<App>::new().hydratable(false).render().await;
+    /// assert!(html.contains(r#"<p lang="fr">Hello</p>"#));
+    /// # }));
+    /// ```
+    #[cfg(feature = "http")]
+    pub fn http(url_template: &str) -> Self {
+        let namespaced = url_template.contains("{ns}");
+        let url_template = url_template.to_string();
+        let load = move |language: String, namespace: Option<String>| {
+            let url = url_template
+                .replace("{lang}", &language)
+                .replace("{ns}", namespace.as_deref().unwrap_or_default());
+            Box::pin(async move {
+                let response = gloo_net::http::Request::get(&url)
+                    .send()
+                    .await
+                    .map_err(|err| format!("Failed to fetch '{}': {}", url, err))?;
+                if !response.ok() {
+                    return Err(format!(
+                        "Failed to fetch '{}': {} {}",
+                        url,
+                        response.status(),
+                        response.status_text()
+                    ));
+                }
+                let body = response
+                    .text()
+                    .await
+                    .map_err(|err| format!("Failed to read '{}': {}", url, err))?;
+                serde_json::from_str::<serde_json::Value>(&body)
+                    .map(Catalog::from)
+                    .map_err(|err| format!("Invalid JSON in '{}': {}", url, err))
+            }) as CatalogFuture
+        };
+        TranslationLoader {
+            load: Rc::new(load),
+            namespaced,
+            server: false,
+        }
+    }
+
+    /// Creates a loader calling [`TranslationSource::load`].
+    ///
+    /// Languages the source cannot load get an empty catalog, so their keys are looked up in
+    /// the source and the enclosing providers.
+    pub fn from_source(source: Arc<dyn TranslationSource>) -> Self {
+        let load = move |language: String, _| {
+            source
+                .load(&language)
+                .unwrap_or_else(|| Box::pin(async { Ok(Catalog::default()) }))
+        };
+        TranslationLoader {
+            load: Rc::new(load),
+            namespaced: false,
+            server: true,
+        }
+    }
+
+    /// Loads the catalog of a language.
+    pub async fn load(&self, language: &str) -> Result<Catalog, String> {
+        (self.load)(language.to_string(), None).await
+    }
+
+    /// Loads one namespace of a language.
+    pub async fn load_namespace(&self, language: &str, namespace: &str) -> Result<Catalog, String> {
+        (self.load)(language.to_string(), Some(namespace.to_string())).await
+    }
+
+    /// Returns whether the loader fetches catalogs one namespace at a time.
+    pub fn is_namespaced(&self) -> bool {
+        self.namespaced
+    }
+
+    /// Returns whether the loader runs during server-side rendering. HTTP loaders only run in
+    /// the browser.
+    pub fn runs_on_server(&self) -> bool {
+        self.server
+    }
+
+    #[cfg(feature = "yew")]
+    pub(crate) async fn load_catalog(
+        &self,
+        (language, namespace): CatalogId,
+    ) -> Result<Catalog, String> {
+        (self.load)(language, namespace).await
+    }
+}
+
+impl PartialEq for TranslationLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.load, &other.load)
+            && self.namespaced == other.namespaced
+            && self.server == other.server
+    }
+}
+
+impl fmt::Debug for TranslationLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TranslationLoader").finish_non_exhaustive()
+    }
+}
+
+/// The handle through which a `YewI18n` asks its provider to load a language.
+#[cfg(feature = "yew")]
+#[derive(Clone)]
+pub(crate) struct LoaderHandle {
+    pub(crate) request: Callback<CatalogId>,
+    pub(crate) waiters: Rc<RefCell<Vec<SuspensionHandle>>>,
+    pub(crate) namespaced: bool,
+}
+
+#[cfg(feature = "yew")]
+impl LoaderHandle {
+    /// Returns a suspension resumed once every pending load has finished.
+    pub(crate) fn suspend(&self) -> Suspension {
+        let (suspension, handle) = Suspension::new();
+        self.waiters.borrow_mut().push(handle);
+        suspension
+    }
+}
+
+#[cfg(feature = "yew")]
+impl PartialEq for LoaderHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.request == other.request
+            && Rc::ptr_eq(&self.waiters, &other.waiters)
+            && self.namespaced == other.namespaced
+    }
+}
+
+/// The catalogs loaded by `I18nProvider`, along with the languages being fetched.
+///
+/// Each field is shared with the `YewI18n` instances of the provider, and copied only when an
+/// action modifies it.
+#[cfg(feature = "yew")]
+#[derive(Clone, PartialEq, Default)]
+pub(crate) struct Catalogs {
+    pub(crate) translations: Arc<HashMap<String, Catalog>>,
+    pub(crate) loading: Rc<HashSet<CatalogId>>,
+    pub(crate) errors: Rc<HashMap<CatalogId, String>>,
+    /// The number of times the loader changed. Actions of an older loader are dropped.
+    pub(crate) generation: usize,
+}
+
+/// Changes to the loaded catalogs, tagged with the generation of the loader they come from.
+#[cfg(feature = "yew")]
+pub(crate) enum CatalogAction {
+    Started(usize, CatalogId),
+    Loaded(usize, CatalogId, Catalog),
+    Failed(usize, CatalogId, String),
+    /// Starts a new generation when the loader changes, discarding the catalogs loaded by the
+    /// previous one so that every catalog comes from the current loader.
+    Reset,
+}
+
+#[cfg(feature = "yew")]
+impl Reducible for Catalogs {
+    type Action = CatalogAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut catalogs = (*self).clone();
+        match action {
+            CatalogAction::Started(generation, _)
+            | CatalogAction::Loaded(generation, ..)
+            | CatalogAction::Failed(generation, ..)
+                if generation != catalogs.generation =>
+            {
+                return self;
+            }
+            CatalogAction::Started(_, id) => {
+                Rc::make_mut(&mut catalogs.errors).remove(&id);
+                Rc::make_mut(&mut catalogs.loading).insert(id);
+            }
+            CatalogAction::Loaded(_, id, value) => {
+                Rc::make_mut(&mut catalogs.loading).remove(&id);
+                merge_catalog(Arc::make_mut(&mut catalogs.translations), id, value);
+            }
+            CatalogAction::Failed(_, id, err) => {
+                Rc::make_mut(&mut catalogs.loading).remove(&id);
+                Rc::make_mut(&mut catalogs.errors).insert(id, err);
+            }
+            CatalogAction::Reset => {
+                catalogs = Catalogs {
+                    generation: catalogs.generation + 1,
+                    ..Default::default()
+                };
+            }
+        }
+        Rc::new(catalogs)
+    }
+}
+
+/// Stores a loaded catalog, under its namespace for namespaced catalogs.
+#[cfg(feature = "yew")]
+pub(crate) fn merge_catalog(
+    translations: &mut HashMap<String, Catalog>,
+    id: CatalogId,
+    catalog: Catalog,
+) {
+    match id {
+        (language, None) => {
+            translations.insert(language, catalog);
+        }
+        (language, Some(namespace)) => {
+            let table = translations.entry(language).or_default().table_mut();
+            table.insert(namespace, catalog);
+        }
+    }
+}
+
+/// Returns the loaded catalogs with the given translations layered on top, language by
+/// language and key by key, so namespaces loaded for a language provided in `translations`
+/// are kept.
+#[cfg(feature = "yew")]
+pub(crate) fn layer_catalogs(
+    loaded: &HashMap<String, Catalog>,
+    translations: &HashMap<String, Catalog>,
+) -> HashMap<String, Catalog> {
+    let mut catalogs = loaded.clone();
+    for (language, catalog) in translations {
+        match (catalogs.get_mut(language), catalog) {
+            (Some(Catalog::Table(base)), Catalog::Table(catalog)) => {
+                base.extend(catalog.clone());
+            }
+            _ => {
+                catalogs.insert(language.clone(), catalog.clone());
+            }
+        }
+    }
+    catalogs
+}
@@ -0,0 +1,428 @@
+//! Sanitized HTML and Markdown rendering of translations.
+//!
+//! Messages are parsed into a tree of whitelisted elements before being turned into Yew
+//! nodes, never injected as raw HTML. Unknown tags are dropped while their text is kept,
+//! `script` and `style` elements are dropped along with their content, attributes other than
+//! the `href` of links are discarded, and links with a scheme other than `http`, `https`,
+//! `mailto` or `tel` are rendered without a target. Placeholders are interpolated after
+//! parsing, so arguments always render as text.
+
+use crate::message;
+use yew::virtual_dom::{VTag, VText};
+use yew::Html;
+
+/// Elements kept by the HTML sanitizer.
+const ALLOWED: [&str; 19] = [
+    "a",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "i",
+    "li",
+    "ol",
+    "p",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "u",
+    "ul",
+];
+/// Elements dropped along with their content.
+const DROPPED: [&str; 2] = ["script", "style"];
+/// Elements without content.
+const VOID: [&str; 1] = ["br"];
+
+/// A sanitized message.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Element {
+        name: &'static str,
+        href: Option<String>,
+        children: Vec<Node>,
+    },
+}
+
+/// Renders a message containing a whitelisted subset of HTML.
+pub(crate) fn html(text: &str, args: &[(&str, &str)]) -> Html {
+    render(&parse_html(text), args)
+}
+
+/// Renders a message written in inline Markdown: `**strong**`, `*emphasis*`, `` `code` ``,
+/// `[links](url)` and line breaks.
+pub(crate) fn markdown(text: &str, args: &[(&str, &str)]) -> Html {
+    let lines: Vec<Vec<Node>> = text.lines().map(parse_markdown).collect();
+    let mut nodes = Vec::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        if index > 0 {
+            nodes.push(element("br", None, Vec::new()));
+        }
+        nodes.extend(line);
+    }
+    render(&nodes, args)
+}
+
+fn element(name: &'static str, href: Option<String>, children: Vec<Node>) -> Node {
+    Node::Element {
+        name,
+        href,
+        children,
+    }
+}
+
+fn allowed(name: &str) -> Option<&'static str> {
+    ALLOWED
+        .iter()
+        .find(|allowed| allowed.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Returns the URL if it is relative or uses a safe scheme.
+fn safe_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let normalized: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let safe = match normalized.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            ["http", "https", "mailto", "tel"].contains(&scheme)
+        }
+        _ => true,
+    };
+    safe.then(|| url.to_string())
+}
+
+/// An element being parsed.
+struct Open {
+    /// The allowed element, `None` for unknown tags whose content is kept.
+    element: Option<&'static str>,
+    name: String,
+    href: Option<String>,
+    children: Vec<Node>,
+}
+
+fn parse_html(text: &str) -> Vec<Node> {
+    let mut stack = vec![Open {
+        element: None,
+        name: String::new(),
+        href: None,
+        children: Vec::new(),
+    }];
+    let mut rest = text;
+
+    while let Some(index) = rest.find('<') {
+        push_text(children(&mut stack), &rest[..index]);
+        let tail = &rest[index..];
+        let Some(end) = tail.find('>') else {
+            push_text(children(&mut stack), tail);
+            rest = "";
+            break;
+        };
+        let tag = &tail[1..end];
+        rest = &tail[end + 1..];
+
+        if tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            let Some(position) = stack
+                .iter()
+                .skip(1)
+                .rposition(|open| open.name.eq_ignore_ascii_case(name))
+            else {
+                continue;
+            };
+            // Elements left open inside the closed one are closed with it.
+            while stack.len() > position + 1 {
+                close(&mut stack);
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        if DROPPED
+            .iter()
+            .any(|dropped| dropped.eq_ignore_ascii_case(name))
+        {
+            let closing = format!("</{}", name.to_ascii_lowercase());
+            rest = match rest.to_ascii_lowercase().find(&closing) {
+                Some(start) => rest[start..]
+                    .find('>')
+                    .map_or("", |end| &rest[start + end + 1..]),
+                None => "",
+            };
+            continue;
+        }
+
+        let allowed = allowed(name);
+        let href = match allowed {
+            Some("a") => attribute(attributes, "href"),
+            _ => None,
+        };
+        if self_closing || allowed.is_some_and(|name| VOID.contains(&name)) {
+            if let Some(name) = allowed {
+                children(&mut stack).push(element(name, href, Vec::new()));
+            }
+        } else {
+            stack.push(Open {
+                element: allowed,
+                name: name.to_string(),
+                href,
+                children: Vec::new(),
+            });
+        }
+    }
+    push_text(children(&mut stack), rest);
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn children(stack: &mut [Open]) -> &mut Vec<Node> {
+    &mut stack.last_mut().expect("the root is never popped").children
+}
+
+/// Closes the innermost open element.
+fn close(stack: &mut Vec<Open>) {
+    let open = stack.pop().expect("checked by the caller");
+    let nodes = children(stack);
+    match open.element {
+        Some(name) => nodes.push(element(name, open.href, open.children)),
+        None => {
+            for child in open.children {
+                push_node(nodes, child);
+            }
+        }
+    }
+}
+
+/// Returns the decoded value of an attribute.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..end];
+        rest = rest[end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, after) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let inner = &after[1..];
+                        let end = inner.find(quote).unwrap_or(inner.len());
+                        (&inner[..end], inner.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => {
+                        let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                        (&after[..end], &after[end..])
+                    }
+                };
+                rest = after.trim_start();
+                value
+            }
+            None => "",
+        };
+        if key.eq_ignore_ascii_case(name) {
+            return Some(decode_entities(value));
+        }
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('&') {
+        output.push_str(&rest[..index]);
+        let tail = &rest[index..];
+        let decoded = tail.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &tail[1..end];
+            let c = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => entity.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                    };
+                    char::from_u32(code?)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                output.push(c);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn push_text(nodes: &mut Vec<Node>, text: &str) {
+    push_node(nodes, Node::Text(decode_entities(text)));
+}
+
+/// A node of a parsed message, which may be text.
+pub(crate) trait TextNode {
+    /// Returns the text of the node, if it is a text node.
+    fn text_mut(&mut self) -> Option<&mut String>;
+}
+
+impl TextNode for Node {
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Node::Text(text) => Some(text),
+            Node::Element { .. } => None,
+        }
+    }
+}
+
+/// Appends a node, merging adjacent text.
+pub(crate) fn push_node<N: TextNode>(nodes: &mut Vec<N>, mut node: N) {
+    let Some(text) = node.text_mut() else {
+        nodes.push(node);
+        return;
+    };
+    if text.is_empty() {
+        return;
+    }
+    match nodes.last_mut().and_then(TextNode::text_mut) {
+        Some(last) => last.push_str(text),
+        None => nodes.push(node),
+    }
+}
+
+/// Parses one line of inline Markdown.
+fn parse_markdown(text: &str) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '\\' => rest[1..].chars().next().map(|escaped| {
+                let len = 1 + escaped.len_utf8();
+                (Node::Text(escaped.to_string()), len)
+            }),
+            '`' => rest[1..].find('`').map(|end| {
+                (
+                    element("code", None, vec![Node::Text(rest[1..end + 1].to_string())]),
+                    end + 2,
+                )
+            }),
+            '*' | '_' => {
+                let strong = if c == '*' { "**" } else { "__" };
+                let (name, delimiter) = if rest.starts_with(strong) {
+                    ("strong", strong)
+                } else {
+                    ("em", &rest[..1])
+                };
+                let inner = &rest[delimiter.len()..];
+                // As in CommonMark, `_` only delimits emphasis at word boundaries, so that
+                // `snake_case_name` is kept as is.
+                let in_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+                let end = if c == '*' {
+                    inner.find(delimiter).filter(|end| *end > 0)
+                } else if in_word(text[..text.len() - rest.len()].chars().next_back()) {
+                    None
+                } else {
+                    inner
+                        .match_indices(delimiter)
+                        .map(|(end, _)| end)
+                        .find(|end| {
+                            *end > 0 && !in_word(inner[end + delimiter.len()..].chars().next())
+                        })
+                };
+                end.map(|end| {
+                    let children = parse_markdown(&inner[..end]);
+                    (element(name, None, children), 2 * delimiter.len() + end)
+                })
+            }
+            '[' => link(rest),
+            _ => None,
+        };
+
+        match parsed {
+            Some((node, len)) => {
+                if let Node::Text(text) = node {
+                    plain.push_str(&text);
+                } else {
+                    push_node(&mut nodes, Node::Text(std::mem::take(&mut plain)));
+                    nodes.push(node);
+                }
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    push_node(&mut nodes, Node::Text(plain));
+    nodes
+}
+
+/// Parses a `[text](url)` link at the start of `text`, returning it and its length.
+fn link(text: &str) -> Option<(Node, usize)> {
+    let label_end = text.find("](")?;
+    let url_len = text[label_end + 2..].find(')')?;
+    let url = &text[label_end + 2..label_end + 2 + url_len];
+    let children = parse_markdown(&text[1..label_end]);
+    Some((
+        element("a", Some(url.to_string()), children),
+        label_end + 3 + url_len,
+    ))
+}
+
+fn render(nodes: &[Node], args: &[(&str, &str)]) -> Html {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => Html::from(VText::new(message::format(text, args).into_owned())),
+            Node::Element {
+                name,
+                href,
+                children,
+            } => {
+                let mut tag = VTag::new(*name);
+                // Links are checked once interpolated, as arguments may hold URLs.
+                if let Some(href) = href
+                    .as_deref()
+                    .and_then(|href| safe_url(&message::format(href, args)))
+                {
+                    tag.add_attribute("href", href);
+                }
+                if !children.is_empty() {
+                    tag.add_child(render(children, args));
+                }
+                Html::from(tag)
+            }
+        })
+        .collect()
+}
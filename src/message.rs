@@ -0,0 +1,115 @@
+//! Placeholder interpolation for translated messages.
+//!
+//! Messages reference arguments with `{name}`; `{{` and `}}` produce literal braces.
+//! Placeholders without a matching argument are kept as-is so missing values stay visible.
+
+use std::borrow::Cow;
+
+/// A message parsed into text and placeholders, formatted without parsing it again.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Message {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    /// A placeholder, with the text kept when no argument matches its name.
+    Placeholder {
+        name: String,
+        raw: String,
+    },
+}
+
+impl Message {
+    pub(crate) fn parse(source: String) -> Self {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = source.as_str();
+
+        while let Some(index) = rest.find(['{', '}']) {
+            text.push_str(&rest[..index]);
+            let tail = &rest[index..];
+
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                text.push_str(&tail[..1]);
+                rest = &tail[2..];
+            } else if let Some((name, after)) = placeholder(tail) {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Placeholder {
+                    name: name.to_string(),
+                    raw: tail[..tail.len() - after.len()].to_string(),
+                });
+                rest = after;
+            } else {
+                text.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+
+        text.push_str(rest);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Message { source, segments }
+    }
+
+    /// Returns the message as written in the catalog.
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Replaces the placeholders with the given arguments.
+    ///
+    /// Messages without placeholders are returned without allocating.
+    pub(crate) fn format(&self, args: &[(&str, &str)]) -> Cow<'_, str> {
+        match self.segments.as_slice() {
+            [] => Cow::Borrowed(""),
+            [Segment::Text(text)] => Cow::Borrowed(text),
+            segments => {
+                let mut output = String::with_capacity(self.source.len());
+                for segment in segments {
+                    match segment {
+                        Segment::Text(text) => output.push_str(text),
+                        Segment::Placeholder { name, raw } => {
+                            match args.iter().find(|(arg, _)| *arg == name) {
+                                Some((_, value)) => output.push_str(value),
+                                None => output.push_str(raw),
+                            }
+                        }
+                    }
+                }
+                Cow::Owned(output)
+            }
+        }
+    }
+}
+
+/// Replaces the `{name}` placeholders of a message with the given arguments.
+///
+/// Messages without braces are returned as-is, without allocating.
+pub(crate) fn format<'a>(message: &'a str, args: &[(&str, &str)]) -> Cow<'a, str> {
+    if !message.contains(['{', '}']) {
+        return Cow::Borrowed(message);
+    }
+    Cow::Owned(
+        Message::parse(message.to_string())
+            .format(args)
+            .into_owned(),
+    )
+}
+
+/// Parses a `{name}` placeholder at the start of `text`, returning its name and the rest.
+fn placeholder(text: &str) -> Option<(&str, &str)> {
+    let inner = text.strip_prefix('{')?;
+    let end = inner.find('}')?;
+    let name = inner[..end].trim();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    valid.then(|| (name, &inner[end + 1..]))
+}
@@ -0,0 +1,215 @@
+//! Locale negotiation for server-side rendered requests.
+//!
+//! A [`LocaleNegotiator`] picks the language of a request from, in order of precedence, a
+//! supported language prefix in the URL path (`/fr/...`), a locale cookie, and the
+//! `Accept-Language` header, falling back to the default language. The result is meant to
+//! be passed to the `language` prop of an SSR-rendered `I18nProvider`.
+//!
+//! With the `axum` or `actix-web` feature enabled, [`RequestLocale`] can be used directly as
+//! a request extractor, and a `negotiate_locale` middleware stores it in the extensions of
+//! every request.
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+
+/// Where the language of a request was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocaleSource {
+    /// A language prefix in the URL path.
+    Path,
+    /// The locale cookie.
+    Cookie,
+    /// The `Accept-Language` header.
+    Header,
+    /// None of the above matched a supported language.
+    Default,
+}
+
+/// The language negotiated for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestLocale {
+    /// The negotiated language code, always one of the supported languages.
+    pub language: String,
+    /// Where the language was found.
+    pub source: LocaleSource,
+}
+
+/// Picks the language of a request among the supported languages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleNegotiator {
+    supported_languages: Vec<String>,
+    cookie_name: String,
+    path_prefix: bool,
+}
+
+impl LocaleNegotiator {
+    /// Creates a negotiator for the given languages, the first one being the default.
+    ///
+    /// The locale cookie is named `lang` and URL path prefixes are enabled by default.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the negotiator, or an error message if no language is given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::negotiation::{LocaleNegotiator, LocaleSource};
+    ///
+    /// let negotiator = LocaleNegotiator::new(vec!["en", "fr", "de"]).unwrap();
+    ///
+    /// let locale = negotiator.negotiate("/products", None, Some("de-CH, fr;q=0.8"));
+    /// assert_eq!(locale.language, "de");
+    /// assert_eq!(locale.source, LocaleSource::Header);
+    ///
+    /// let locale = negotiator.negotiate("/fr/products", Some("lang=de"), Some("en"));
+    /// assert_eq!(locale.language, "fr");
+    /// assert_eq!(locale.source, LocaleSource::Path);
+    ///
+    /// let locale = negotiator.negotiate("/products", Some("theme=dark; lang=de"), Some("en"));
+    /// assert_eq!(locale.language, "de");
+    /// assert_eq!(locale.source, LocaleSource::Cookie);
+    ///
+    /// assert!(LocaleNegotiator::new(vec![]).is_err());
+    /// ```
+    pub fn new(supported_languages: Vec<&str>) -> Result<Self, String> {
+        if supported_languages.is_empty() {
+            return Err("You must add at least one supported language".to_string());
+        }
+        Ok(LocaleNegotiator {
+            supported_languages: supported_languages
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            cookie_name: "lang".to_string(),
+            path_prefix: true,
+        })
+    }
+
+    /// Sets the name of the locale cookie.
+    pub fn with_cookie_name(mut self, cookie_name: &str) -> Self {
+        self.cookie_name = cookie_name.to_string();
+        self
+    }
+
+    /// Enables or disables reading the language from the first segment of the URL path.
+    pub fn with_path_prefix(mut self, path_prefix: bool) -> Self {
+        self.path_prefix = path_prefix;
+        self
+    }
+
+    /// Returns the supported languages, the default one first.
+    pub fn supported_languages(&self) -> &[String] {
+        &self.supported_languages
+    }
+
+    /// Negotiates the language of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The URL path of the request.
+    /// * `cookie` - The value of the `Cookie` header, if any.
+    /// * `accept_language` - The value of the `Accept-Language` header, if any.
+    ///
+    /// # Returns
+    ///
+    /// The negotiated locale, the default language if nothing matched.
+    pub fn negotiate(
+        &self,
+        path: &str,
+        cookie: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> RequestLocale {
+        let from_path = self
+            .path_prefix
+            .then(|| path.trim_start_matches('/').split('/').next())
+            .flatten()
+            .and_then(|segment| self.find_exact(segment));
+        if let Some(language) = from_path {
+            return self.locale(language, LocaleSource::Path);
+        }
+
+        let from_cookie = cookie
+            .and_then(|cookie| cookie_value(cookie, &self.cookie_name))
+            .and_then(|value| self.find(value));
+        if let Some(language) = from_cookie {
+            return self.locale(language, LocaleSource::Cookie);
+        }
+
+        let from_header = accept_language.and_then(|header| {
+            parse_accept_language(header)
+                .into_iter()
+                .find_map(|(tag, _)| self.find(&tag))
+        });
+        if let Some(language) = from_header {
+            return self.locale(language, LocaleSource::Header);
+        }
+
+        // `new` rejects an empty list of supported languages.
+        self.locale(&self.supported_languages[0], LocaleSource::Default)
+    }
+
+    fn locale(&self, language: &str, source: LocaleSource) -> RequestLocale {
+        RequestLocale {
+            language: language.to_string(),
+            source,
+        }
+    }
+
+    fn find_exact(&self, tag: &str) -> Option<&str> {
+        self.supported_languages
+            .iter()
+            .find(|language| language.eq_ignore_ascii_case(tag))
+            .map(String::as_str)
+    }
+
+    /// Finds a supported language matching a language tag exactly, then by primary subtag.
+    fn find(&self, tag: &str) -> Option<&str> {
+        let primary = |tag: &str| tag.split(['-', '_']).next().unwrap_or_default().to_string();
+        self.find_exact(tag).or_else(|| {
+            let tag = primary(tag);
+            self.supported_languages
+                .iter()
+                .find(|language| primary(language).eq_ignore_ascii_case(&tag))
+                .map(String::as_str)
+        })
+    }
+}
+
+/// Parses an `Accept-Language` header into language tags sorted by decreasing quality.
+///
+/// The wildcard `*` and tags with a quality of zero are left out.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::negotiation::parse_accept_language;
+///
+/// let languages = parse_accept_language("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5");
+/// assert_eq!(languages[0], ("fr-CH".to_string(), 1.0));
+/// assert_eq!(languages.len(), 4);
+/// ```
+pub fn parse_accept_language(header: &str) -> Vec<(String, f32)> {
+    let mut languages: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then(|| (tag.to_string(), quality))
+        })
+        .collect();
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+    languages
+}
+
+fn cookie_value<'a>(cookie: &'a str, name: &str) -> Option<&'a str> {
+    cookie.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key.trim() == name).then(|| value.trim().trim_matches('"'))
+    })
+}
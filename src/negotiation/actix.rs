@@ -0,0 +1,125 @@
+//! Extracts the [`RequestLocale`] of an actix-web request.
+//!
+//! The negotiator is taken from the application data, registered either directly with
+//! `App::app_data` or wrapped in `web::Data`. To negotiate the locale of every request
+//! instead, for instance for other middleware, wrap the app in [`negotiate_locale`].
+//!
+//! ```
+//! # actix_web::rt::System::new().block_on(async {
+//! use actix_web::{test, web, App};
+//! use yew_i18n::negotiation::{LocaleNegotiator, RequestLocale};
+//!
+//! async fn page(locale: RequestLocale) -> String {
+//!     // Render the app with `<I18nProvider language={locale.language} ...>`.
+//!     locale.language
+//! }
+//!
+//! let app = test::init_service(
+//!     App::new()
+//!         .app_data(web::Data::new(LocaleNegotiator::new(vec!["en", "fr", "de"]).unwrap()))
+//!         .route("/{tail:.*}", web::get().to(page)),
+//! )
+//! .await;
+//!
+//! let request = test::TestRequest::get()
+//!     .uri("/de/products")
+//!     .insert_header(("accept-language", "fr"))
+//!     .to_request();
+//! let body = test::call_and_read_body(&app, request).await;
+//! assert_eq!(body, "de");
+//! # });
+//! ```
+
+use super::{LocaleNegotiator, RequestLocale};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use actix_web::middleware::Next;
+use actix_web::{error, web, Error, FromRequest, HttpMessage, HttpRequest};
+use std::future::{ready, Ready};
+
+/// Negotiates the locale of every request and stores it in the request extensions.
+///
+/// Add it with `actix_web::middleware::from_fn`. Handlers and later middleware then read the
+/// locale with the [`RequestLocale`] extractor or from the request extensions.
+///
+/// # Examples
+///
+/// ```
+/// # actix_web::rt::System::new().block_on(async {
+/// use actix_web::{middleware, test, web, App};
+/// use yew_i18n::negotiation::actix::negotiate_locale;
+/// use yew_i18n::negotiation::{LocaleNegotiator, RequestLocale};
+///
+/// async fn page(locale: web::ReqData<RequestLocale>) -> String {
+///     locale.into_inner().language
+/// }
+///
+/// let app = test::init_service(
+///     App::new()
+///         .app_data(web::Data::new(LocaleNegotiator::new(vec!["en", "fr"]).unwrap()))
+///         .wrap(middleware::from_fn(negotiate_locale))
+///         .route("/{tail:.*}", web::get().to(page)),
+/// )
+/// .await;
+///
+/// let request = test::TestRequest::get().uri("/fr/products").to_request();
+/// let body = test::call_and_read_body(&app, request).await;
+/// assert_eq!(body, "fr");
+/// # });
+/// ```
+pub async fn negotiate_locale(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let locale = negotiate(req.request())?;
+    req.extensions_mut().insert(locale);
+    next.call(req).await
+}
+
+impl FromRequest for RequestLocale {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        if let Some(locale) = req.extensions().get::<RequestLocale>() {
+            return ready(Ok(locale.clone()));
+        }
+
+        let locale = negotiate(req);
+        if let Ok(locale) = &locale {
+            req.extensions_mut().insert(locale.clone());
+        }
+        ready(locale)
+    }
+}
+
+/// Negotiates the locale of a request with the negotiator of the application data.
+fn negotiate(req: &HttpRequest) -> Result<RequestLocale, Error> {
+    let negotiator = req
+        .app_data::<web::Data<LocaleNegotiator>>()
+        .map(|data| data.get_ref())
+        .or_else(|| req.app_data::<LocaleNegotiator>())
+        .ok_or_else(|| {
+            error::ErrorInternalServerError(
+                "No LocaleNegotiator registered in the application data",
+            )
+        })?;
+
+    let cookie = req
+        .headers()
+        .get_all(COOKIE)
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let accept_language = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+
+    Ok(negotiator.negotiate(
+        req.path(),
+        Some(cookie.as_str()).filter(|cookie| !cookie.is_empty()),
+        accept_language,
+    ))
+}
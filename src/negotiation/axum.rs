@@ -0,0 +1,117 @@
+//! Extracts the [`RequestLocale`] of an axum request.
+//!
+//! The negotiator is taken from the router state, so add it to your state and implement
+//! `FromRef` if the state holds more than the negotiator. To negotiate the locale of every
+//! request instead, for instance for other middleware, add [`negotiate_locale`] as a layer.
+//!
+//! ```
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! use axum::{body::Body, http::Request, routing::get, Router};
+//! use tower::ServiceExt;
+//! use yew_i18n::negotiation::{LocaleNegotiator, RequestLocale};
+//!
+//! async fn page(locale: RequestLocale) -> String {
+//!     // Render the app with `<I18nProvider language={locale.language} ...>`.
+//!     locale.language
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/", get(page))
+//!     .with_state(LocaleNegotiator::new(vec!["en", "fr"]).unwrap());
+//!
+//! let request = Request::get("/")
+//!     .header("accept-language", "fr-FR, en;q=0.5")
+//!     .body(Body::empty())
+//!     .unwrap();
+//! let response = app.oneshot(request).await.unwrap();
+//! let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+//! assert_eq!(body, "fr");
+//! # });
+//! ```
+
+use super::{LocaleNegotiator, RequestLocale};
+use axum::extract::{FromRef, FromRequestParts, Request, State};
+use axum::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::convert::Infallible;
+
+/// Negotiates the locale of every request and stores it in the request extensions.
+///
+/// Add it with `axum::middleware::from_fn_with_state`. Handlers then read the locale with
+/// the [`RequestLocale`] extractor or with `Extension<RequestLocale>`, which does not need
+/// the negotiator in the router state.
+///
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use axum::{body::Body, http::Request, middleware, routing::get, Extension, Router};
+/// use tower::ServiceExt;
+/// use yew_i18n::negotiation::axum::negotiate_locale;
+/// use yew_i18n::negotiation::{LocaleNegotiator, RequestLocale};
+///
+/// async fn page(Extension(locale): Extension<RequestLocale>) -> String {
+///     locale.language
+/// }
+///
+/// let negotiator = LocaleNegotiator::new(vec!["en", "fr"]).unwrap();
+/// let app = Router::new()
+///     .route("/{*path}", get(page))
+///     .layer(middleware::from_fn_with_state(negotiator, negotiate_locale));
+///
+/// let request = Request::get("/fr/products").body(Body::empty()).unwrap();
+/// let response = app.oneshot(request).await.unwrap();
+/// let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+/// assert_eq!(body, "fr");
+/// # });
+/// ```
+pub async fn negotiate_locale(
+    State(negotiator): State<LocaleNegotiator>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let locale = negotiate(&negotiator, request.uri().path(), request.headers());
+    request.extensions_mut().insert(locale);
+    next.run(request).await
+}
+
+impl<S> FromRequestParts<S> for RequestLocale
+where
+    LocaleNegotiator: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(locale) = parts.extensions.get::<RequestLocale>() {
+            return Ok(locale.clone());
+        }
+
+        let negotiator = LocaleNegotiator::from_ref(state);
+        let locale = negotiate(&negotiator, parts.uri.path(), &parts.headers);
+        parts.extensions.insert(locale.clone());
+        Ok(locale)
+    }
+}
+
+/// Negotiates the locale of a request from its path and headers.
+fn negotiate(negotiator: &LocaleNegotiator, path: &str, headers: &HeaderMap) -> RequestLocale {
+    let cookie = headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let accept_language = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+
+    negotiator.negotiate(
+        path,
+        Some(cookie.as_str()).filter(|cookie| !cookie.is_empty()),
+        accept_language,
+    )
+}
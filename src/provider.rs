@@ -0,0 +1,554 @@
+//! The Yew adapter: `I18nProvider` and the translation hooks.
+//!
+//! The provider shares a [`YewI18n`] with its children through a context, loads missing
+//! catalogs with its loader, and prepares its state for hydration. Everything else, from
+//! catalog lookup to message formatting, is framework-independent.
+
+use crate::binary::BinaryCatalog;
+use crate::catalog::CatalogSet;
+use crate::hydration;
+use crate::loader::{layer_catalogs, CatalogAction, CatalogId, Catalogs, LoaderHandle};
+use crate::{Catalog, TranslationLoader, TranslationSource, YewI18n};
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+use yew::html::IntoPropValue;
+use yew::prelude::*;
+use yew::suspense::SuspensionResult;
+
+/// The `translations` of an `I18nProvider`, shared between renders instead of copied.
+///
+/// Passing a `HashMap<String, Catalog>` converts it. Passing clones of the same
+/// `Translations`, for instance one kept in a `use_memo`, lets the provider compare its
+/// catalogs by pointer, so re-rendering it does not depend on the size of the catalogs.
+///
+/// # Examples
+///
+/// ```
+/// use yew_i18n::{Catalog, Translations};
+/// use std::collections::HashMap;
+///
+/// let mut catalogs = HashMap::new();
+/// catalogs.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+///
+/// let translations = Translations::from(catalogs);
+/// assert_eq!(translations.clone(), translations);
+/// assert!(translations.contains_key("en"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Translations(Arc<HashMap<String, Catalog>>);
+
+impl Translations {
+    /// Returns the shared catalogs.
+    pub fn shared(&self) -> Arc<HashMap<String, Catalog>> {
+        Arc::clone(&self.0)
+    }
+}
+
+impl PartialEq for Translations {
+    /// Compares by pointer first, and by content only for catalogs built separately.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Deref for Translations {
+    type Target = HashMap<String, Catalog>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<HashMap<String, Catalog>> for Translations {
+    fn from(translations: HashMap<String, Catalog>) -> Self {
+        Translations(Arc::new(translations))
+    }
+}
+
+impl From<Arc<HashMap<String, Catalog>>> for Translations {
+    fn from(translations: Arc<HashMap<String, Catalog>>) -> Self {
+        Translations(translations)
+    }
+}
+
+impl IntoPropValue<Translations> for HashMap<String, Catalog> {
+    fn into_prop_value(self) -> Translations {
+        self.into()
+    }
+}
+
+impl IntoPropValue<Translations> for Arc<HashMap<String, Catalog>> {
+    fn into_prop_value(self) -> Translations {
+        self.into()
+    }
+}
+
+/// Configuration for the YewI18nProvider component.
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct YewI18nProviderConfig {
+    /// List of supported languages. Defaults to English and French if not specified.
+    #[prop_or_else(|| vec!["en", "fr"])]
+    pub supported_languages: Vec<&'static str>,
+    /// Translations for different languages, represented as a mapping from language codes to catalogs.
+    #[prop_or_default]
+    pub translations: Translations,
+    /// Compact binary catalogs produced by `yew-i18n-catalog`, looked up after `translations`.
+    #[prop_or_default]
+    pub binary: Option<BinaryCatalog<'static>>,
+    /// A translation backend looked up after `translations` and before `binary`. Without a
+    /// `loader`, the languages it is missing are loaded with [`TranslationSource::load`].
+    #[prop_or_default]
+    pub source: Option<Arc<dyn TranslationSource>>,
+    /// Loader fetching the catalog of a language missing from `translations` when it is first selected.
+    ///
+    /// Replacing the loader discards the catalogs loaded by the previous one, along with the
+    /// results it has yet to return, and loads them again from the new loader.
+    #[prop_or_default]
+    pub loader: Option<TranslationLoader>,
+    /// The language to start with, for instance the one negotiated for a server-side rendered
+    /// request. Defaults to the first supported language.
+    #[prop_or_default]
+    pub language: Option<AttrValue>,
+    /// Namespaces used by the whole application. A namespaced loader loads them for every
+    /// language, and keys without a `namespace:` prefix are also looked up in them.
+    #[prop_or_default]
+    pub namespaces: Vec<&'static str>,
+    /// Rendered instead of the children when the provider cannot be initialized, or when a
+    /// catalog of the current language fails to load.
+    #[prop_or_default]
+    pub fallback: Option<Html>,
+    /// Called with the error when the provider cannot be initialized or a catalog fails to
+    /// load.
+    #[prop_or_default]
+    pub on_error: Option<Callback<String>>,
+    /// Wraps the provider in a `Suspense` of its own. Rendering on the server or hydrating
+    /// suspends the provider while its state is prepared, which needs a `Suspense` around it:
+    /// set this when the provider is not already inside one. The boundary also catches the
+    /// suspensions of the children.
+    ///
+    /// With the `ssr` or `hydration` feature, a provider rendered without this prop and outside
+    /// of a `Suspense` makes Yew panic.
+    #[prop_or_default]
+    pub suspense: bool,
+    /// The child components to be wrapped with the YewI18n context.
+    pub children: Html,
+}
+
+/// Yew component for providing the YewI18n context to its children.
+///
+/// The context follows the props: catalogs added to or edited in `translations` show up on
+/// the next render, a new `language` prop is selected, and a change of `supported_languages`
+/// keeps the current language while it is still supported. Children switch the language of
+/// the provider with [`YewI18n::select_language`].
+///
+/// Under Yew's server renderer, the provider renders in the `language` prop, loads its
+/// catalogs with the `loader` before rendering the children, and sends the language and
+/// loaded catalogs to the client for hydration.
+///
+/// # Examples
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use yew::prelude::*;
+/// use yew_i18n::{use_translation, I18nProvider, TranslationLoader};
+/// use serde_json::json;
+///
+/// #[function_component]
+/// fn Greeting() -> Html {
+///     let i18n = use_translation();
+///     html! { <p>{ i18n.t("greeting") }</p> }
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct AppProps {
+///     language: String,
+/// }
+///
+/// #[function_component]
+/// fn App(props: &AppProps) -> Html {
+///     let loader = use_memo((), |_| {
+///         TranslationLoader::new(|language: String| async move {
+///             Ok(match language.as_str() {
+///                 "fr" => json!({ "greeting": "Bonjour" }),
+///                 _ => json!({ "greeting": "Hello" }),
+///             }
+///             .into())
+///         })
+///     });
+///
+///     html! {
+///         <I18nProvider
+///             supported_languages={vec!["en", "fr"]}
+///             language={props.language.clone()}
+///             loader={(*loader).clone()}
+///             suspense=true
+///         >
+///             <Greeting />
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let html = yew::LocalServerRenderer::<App>::with_props(AppProps { language: "fr".into() })
+///     .render()
+///     .await;
+/// assert!(html.contains("<p>Bonjour</p>"));
+/// # }));
+/// ```
+///
+/// # Nesting
+///
+/// A provider nested in another one follows its current language, and looks up keys missing
+/// from its own catalogs in the catalogs of the enclosing provider. A widget can thus bring
+/// its own catalog, adding or overriding messages of the application:
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use std::collections::HashMap;
+/// use yew::prelude::*;
+/// use yew_i18n::{use_translation, I18nProvider};
+/// use serde_json::json;
+///
+/// #[function_component]
+/// fn Widget() -> Html {
+///     let i18n = use_translation();
+///     html! { <p>{ i18n.t("title") }{ " - " }{ i18n.t("app") }</p> }
+/// }
+///
+/// #[function_component]
+/// fn App() -> Html {
+///     let mut app = HashMap::new();
+///     app.insert("fr".to_string(), json!({ "app": "Boutique", "title": "Accueil" }).into());
+///     let mut widget = HashMap::new();
+///     widget.insert("fr".to_string(), json!({ "title": "Calendrier" }).into());
+///
+///     html! {
+///         <I18nProvider supported_languages={vec!["fr"]} translations={app} suspense=true>
+///             <I18nProvider supported_languages={vec!["fr"]} translations={widget}>
+///                 <Widget />
+///             </I18nProvider>
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let html = yew::LocalServerRenderer::<App>::new().render().await;
+/// assert!(html.contains("<p>Calendrier - Boutique</p>"));
+/// # }));
+/// ```
+///
+/// # Errors
+///
+/// The provider never panics on a broken configuration or catalog: when it cannot be
+/// initialized, for instance without supported languages, or when a catalog of the current language fails to load, it renders its
+/// `fallback` instead of its children and reports the error to `on_error`. The fallback sees
+/// the context of the enclosing provider, if any, rather than the failed one. Without a
+/// `fallback`, children still render after a load failure, and can read it from
+/// `YewI18n::load_error`. With the `ssr` or `hydration` feature, it needs a `Suspense`
+/// around it or the `suspense` prop, see [`suspense`](YewI18nProviderConfig::suspense).
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use yew::prelude::*;
+/// use yew_i18n::{use_try_translation, I18nProvider};
+///
+/// #[function_component]
+/// fn Greeting() -> Html {
+///     match use_try_translation() {
+///         Some(i18n) => html! { <p>{ i18n.t("greeting") }</p> },
+///         None => html! { <p>{ "Hello" }</p> },
+///     }
+/// }
+///
+/// #[function_component]
+/// fn App() -> Html {
+///     html! {
+///         <I18nProvider supported_languages={vec![]} fallback={html! { <Greeting /> }} suspense=true>
+///             <p>{ "Never rendered" }</p>
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let html = yew::LocalServerRenderer::<App>::new().render().await;
+/// assert!(html.contains("<p>Hello</p>"));
+/// # }));
+/// ```
+#[function_component(I18nProvider)]
+pub fn i18n_provider(props: &YewI18nProviderConfig) -> Html {
+    let provider = html! { <I18nProviderInner ..props.clone() /> };
+    if props.suspense {
+        html! { <Suspense>{ provider }</Suspense> }
+    } else {
+        provider
+    }
+}
+
+#[function_component(I18nProviderInner)]
+fn i18n_provider_inner(props: &YewI18nProviderConfig) -> HtmlResult {
+    let parent = use_context::<YewI18n>();
+    let prepared = hydration::use_prepared_catalogs(props)?.unwrap_or_default();
+    // The prepared language applies until the `language` prop changes.
+    let selected = use_mut_ref(|| {
+        let language = prepared
+            .language
+            .clone()
+            .or_else(|| hydration::initial_language(props));
+        (props.language.clone(), language)
+    });
+    let language = {
+        let mut selected = selected.borrow_mut();
+        if selected.0 != props.language {
+            *selected = (props.language.clone(), hydration::initial_language(props));
+        }
+        selected.1.clone()
+    };
+    // Children switch the language of the provider with `YewI18n::select_language`.
+    let selector = {
+        let update = use_force_update();
+        use_callback((), move |language: String, _| {
+            let changed = selected.borrow().1.as_deref() != Some(language.as_str());
+            if changed {
+                selected.borrow_mut().1 = Some(language);
+                update.force_update();
+            }
+        })
+    };
+
+    // Loaded catalogs are kept apart from the `translations` prop, so changes to the prop
+    // show up on the next render.
+    let catalogs = use_reducer(|| Catalogs {
+        translations: Arc::new(prepared.translations),
+        ..Default::default()
+    });
+    let supported_languages = use_shared(&props.supported_languages);
+    // The catalogs are compiled again only when the loaded or provided ones change.
+    let translations = {
+        let provided = use_shared(&props.translations).shared();
+        let compiled = use_mut_ref(|| None);
+        let mut compiled = compiled.borrow_mut();
+        match &*compiled {
+            Some((loaded, cached, binary, source, result))
+                if Arc::ptr_eq(loaded, &catalogs.translations)
+                    && Arc::ptr_eq(cached, &provided)
+                    && *binary == props.binary
+                    && *source == props.source =>
+            {
+                Arc::clone(result)
+            }
+            _ => {
+                let layered = if catalogs.translations.is_empty() {
+                    provided.clone()
+                } else {
+                    Arc::new(layer_catalogs(&catalogs.translations, &provided))
+                };
+                let mut sources: Vec<Arc<dyn TranslationSource>> = Vec::new();
+                sources.extend(props.source.clone());
+                if let Some(binary) = props.binary {
+                    sources.push(Arc::new(binary));
+                }
+                let result = Arc::new(CatalogSet::new(layered, sources));
+                *compiled = Some((
+                    catalogs.translations.clone(),
+                    provided,
+                    props.binary,
+                    props.source.clone(),
+                    result.clone(),
+                ));
+                result
+            }
+        }
+    };
+    let waiters = use_mut_ref(Vec::new);
+    let requested = use_mut_ref(HashSet::new);
+    let loader = use_memo(
+        (props.loader.clone(), props.source.clone()),
+        |(loader, source)| {
+            let source = source.clone().map(TranslationLoader::from_source);
+            // On the server, components do not wait for catalogs that only the browser loads.
+            let loader = loader
+                .clone()
+                .filter(|loader| cfg!(target_arch = "wasm32") || loader.runs_on_server());
+            loader.or(source)
+        },
+    );
+    // Catalogs are requested again from a new loader, and the results of the previous one
+    // are dropped.
+    let generation = use_mut_ref(|| 0usize);
+    let reset = {
+        let previous = use_mut_ref(|| loader.clone());
+        let changed = !Rc::ptr_eq(&previous.borrow(), &loader);
+        if changed {
+            *previous.borrow_mut() = loader.clone();
+            *generation.borrow_mut() += 1;
+            requested.borrow_mut().clear();
+            catalogs.dispatch(CatalogAction::Reset);
+        }
+        changed
+    };
+
+    // The latest `on_error` is read when a load fails, so that a new callback on every render
+    // of the parent does not change the context.
+    let on_error = use_mut_ref(|| None);
+    *on_error.borrow_mut() = props.on_error.clone();
+    let request = {
+        let dispatcher = catalogs.dispatcher();
+        use_callback((*loader).clone(), move |id: CatalogId, loader| {
+            let Some(loader) = loader.clone() else {
+                return;
+            };
+            if !requested.borrow_mut().insert(id.clone()) {
+                return;
+            }
+            let current = *generation.borrow();
+            dispatcher.dispatch(CatalogAction::Started(current, id.clone()));
+            let dispatcher = dispatcher.clone();
+            let generation = generation.clone();
+            let requested = requested.clone();
+            let on_error = on_error.clone();
+            yew::platform::spawn_local(async move {
+                let result = loader.load_catalog(id.clone()).await;
+                if *generation.borrow() != current {
+                    return;
+                }
+                match result {
+                    Ok(value) => dispatcher.dispatch(CatalogAction::Loaded(current, id, value)),
+                    Err(err) => {
+                        requested.borrow_mut().remove(&id);
+                        let on_error = on_error.borrow().clone();
+                        if let Some(on_error) = on_error {
+                            on_error.emit(err.clone());
+                        }
+                        dispatcher.dispatch(CatalogAction::Failed(current, id, err));
+                    }
+                }
+            });
+        })
+    };
+
+    let i18n = YewI18n::with_catalogs(supported_languages, translations).map(|mut i18n| {
+        if let Some(language) = &language {
+            let _ = i18n.set_translation_language(language);
+        }
+        i18n.selector = Some(selector);
+        if let Some(parent) = parent {
+            // A nested provider follows the language of the enclosing one, even if missing from
+            // its `supported_languages`, as its messages may be found in the parent's catalogs.
+            i18n.current_language = parent.current_language.clone();
+            if parent.selector.is_some() {
+                i18n.selector = parent.selector.clone();
+            }
+            i18n.parent = Some(Rc::new(parent));
+        }
+        i18n.add_namespaces(&props.namespaces);
+        if let Some(loader) = &*loader {
+            i18n.loader = Some(LoaderHandle {
+                request,
+                waiters: waiters.clone(),
+                namespaced: loader.is_namespaced(),
+            });
+            if !reset {
+                i18n.loading = catalogs.loading.clone();
+                i18n.load_errors = catalogs.errors.clone();
+            }
+        }
+        i18n
+    });
+
+    {
+        let i18n = i18n.clone();
+        let on_error = props.on_error.clone();
+        let deps = (
+            i18n.as_ref().map(|i18n| i18n.current_language.clone()).ok(),
+            props.namespaces.clone(),
+            (*loader).clone(),
+            // Catalogs discarded with a previous loader are requested once they are gone.
+            catalogs.generation,
+        );
+        use_effect_with(deps, move |_| match i18n {
+            Ok(i18n) => i18n.request_load(),
+            Err(err) => {
+                if let Some(on_error) = on_error {
+                    on_error.emit(err);
+                }
+            }
+        });
+    }
+
+    use_effect_with(catalogs.loading.is_empty(), move |idle| {
+        if *idle {
+            // Dropping the handles resumes the suspended components.
+            waiters.borrow_mut().clear();
+        }
+    });
+
+    Ok(match i18n {
+        Ok(i18n) if i18n.load_error().is_none() || props.fallback.is_none() => html! {
+            <ContextProvider<YewI18n> context={i18n}>{ props.children.clone() }</ContextProvider<YewI18n>>
+        },
+        _ => props.fallback.clone().unwrap_or_default(),
+    })
+}
+
+/// Returns a shared copy of a value, kept as long as the value does not change.
+#[hook]
+fn use_shared<T>(value: &T) -> Arc<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    let shared = use_mut_ref(|| Arc::new(value.clone()));
+    let mut shared = shared.borrow_mut();
+    if **shared != *value {
+        *shared = Arc::new(value.clone());
+    }
+    Arc::clone(&shared)
+}
+
+/// Returns the YewI18n context.
+///
+/// # Panics
+///
+/// Panics when called outside of an `I18nProvider`. Use [`use_try_translation`] in components
+/// that may be rendered without one.
+#[hook]
+pub fn use_translation() -> YewI18n {
+    use_context::<YewI18n>().expect("No I18n context provided")
+}
+
+/// Returns the YewI18n context, or `None` outside of an `I18nProvider`.
+///
+/// A provider rendering its `fallback` provides no context of its own: inside the fallback,
+/// this returns the context of the enclosing provider if it is nested, and `None` otherwise.
+///
+/// The fallible counterpart of [`use_translation`], named `use_` first as Yew requires of
+/// every hook.
+#[hook]
+pub fn use_try_translation() -> Option<YewI18n> {
+    use_context::<YewI18n>()
+}
+
+/// Returns the YewI18n context, suspending the component while catalogs are being loaded.
+///
+/// Wrap the component in a `Suspense` to show a fallback until the selected language is
+/// available.
+#[hook]
+pub fn use_translation_suspense() -> SuspensionResult<YewI18n> {
+    use_translation().suspend_while_loading()
+}
+
+/// Returns the YewI18n context for a component using the given namespaces.
+///
+/// With a namespaced loader, the namespaces are loaded for the current language, and for any
+/// language selected later through the returned instance, suspending the component until
+/// they are available. Keys without a `namespace:` prefix are also looked up in them.
+#[hook]
+pub fn use_namespaces(namespaces: &[&str]) -> SuspensionResult<YewI18n> {
+    let mut i18n = use_translation();
+    i18n.add_namespaces(namespaces);
+    i18n.suspend_while_loading()
+}
@@ -0,0 +1,539 @@
+//! Locale-aware routing for yew-router.
+//!
+//! [`LocalizedSwitch`] serves the routes of an application under a locale prefix
+//! (`/fr/products`) or query parameter (`/products?lang=fr`), and sets the language of the
+//! YewI18n context of its children to the one in the URL. Routes are declared without the
+//! prefix, so the same `Routable` enum serves every language. [`LocalizedLink`] builds
+//! links that keep the current locale, and [`use_switch_language`] moves the current page to
+//! another language. Route slugs can be translated through the catalogs as well, see
+//! [`localize_slugs`].
+//!
+//! ```
+//! # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+//! # #[cfg(feature = "ssr")]
+//! # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+//! use std::collections::HashMap;
+//! use serde_json::json;
+//! use yew::prelude::*;
+//! use yew_i18n::router::LocalizedSwitch;
+//! use yew_i18n::{use_translation, I18nProvider};
+//! use yew_router::history::{AnyHistory, MemoryHistory};
+//! use yew_router::prelude::*;
+//!
+//! #[derive(Clone, PartialEq, Routable)]
+//! enum Route {
+//!     #[at("/")]
+//!     Home,
+//! }
+//!
+//! #[function_component]
+//! fn Home() -> Html {
+//!     let i18n = use_translation();
+//!     html! { <h1>{ i18n.t("title") }</h1> }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     let mut translations = HashMap::new();
+//!     translations.insert("en".to_string(), json!({ "title": "Home" }).into());
+//!     translations.insert("fr".to_string(), json!({ "title": "Accueil" }).into());
+//!     let history = AnyHistory::from(MemoryHistory::with_entries(vec!["/fr/"]));
+//!
+//!     html! {
+//!         <I18nProvider supported_languages={vec!["en", "fr"]} translations={translations} suspense=true>
+//!             <Router history={history}>
+//!                 <LocalizedSwitch<Route> render={|route| match route {
+//!                     Route::Home => html! { <Home /> },
+//!                 }} />
+//!             </Router>
+//!         </I18nProvider>
+//!     }
+//! }
+//!
+//! let html = yew::LocalServerRenderer::<App>::new().render().await;
+//! assert!(html.contains("<h1>Accueil</h1>"));
+//! # }));
+//! ```
+
+use crate::catalog::Entry;
+use crate::{use_translation, YewI18n};
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yew_router::AnyRoute;
+
+/// Where the locale is stored in the URL.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LocaleStrategy {
+    /// As the first path segment, as in `/fr/products`.
+    #[default]
+    PathPrefix,
+    /// As a query parameter with the given name, as in `/products?lang=fr`.
+    Query(&'static str),
+}
+
+impl LocaleStrategy {
+    /// Splits a URL into its locale, if it is one of the supported languages, and the path
+    /// and query without it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::router::LocaleStrategy;
+    ///
+    /// let supported = ["en", "fr"];
+    ///
+    /// let (language, path) = LocaleStrategy::PathPrefix.split("/fr/products/42", "", &supported);
+    /// assert_eq!((language.as_deref(), path.as_str()), (Some("fr"), "/products/42"));
+    ///
+    /// let (language, path) = LocaleStrategy::Query("lang").split("/products", "?page=2&lang=fr", &supported);
+    /// assert_eq!((language.as_deref(), path.as_str()), (Some("fr"), "/products?page=2"));
+    /// ```
+    pub fn split(&self, path: &str, query: &str, supported: &[&str]) -> (Option<String>, String) {
+        let query = query.trim_start_matches('?');
+        match self {
+            LocaleStrategy::PathPrefix => {
+                let trimmed = path.trim_start_matches('/');
+                let (segment, rest) = trimmed.split_once('/').unwrap_or((trimmed, ""));
+                let query = if query.is_empty() {
+                    String::new()
+                } else {
+                    format!("?{}", query)
+                };
+                if supported.contains(&segment) {
+                    (Some(segment.to_string()), format!("/{}{}", rest, query))
+                } else {
+                    (None, format!("{}{}", path, query))
+                }
+            }
+            LocaleStrategy::Query(name) => {
+                let mut language = None;
+                let params: Vec<&str> = query
+                    .split('&')
+                    .filter(|param| !param.is_empty())
+                    .filter(|param| match param.split_once('=') {
+                        Some((key, value)) if key == *name && supported.contains(&value) => {
+                            language = Some(value.to_string());
+                            false
+                        }
+                        _ => true,
+                    })
+                    .collect();
+                let path = if params.is_empty() {
+                    path.to_string()
+                } else {
+                    format!("{}?{}", path, params.join("&"))
+                };
+                (language, path)
+            }
+        }
+    }
+
+    /// Adds a locale to a path, which may contain a query string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::router::LocaleStrategy;
+    ///
+    /// assert_eq!(LocaleStrategy::PathPrefix.localize("/products", "fr"), "/fr/products");
+    /// assert_eq!(LocaleStrategy::Query("lang").localize("/products?page=2", "fr"), "/products?page=2&lang=fr");
+    /// ```
+    pub fn localize(&self, path: &str, language: &str) -> String {
+        match self {
+            LocaleStrategy::PathPrefix => {
+                format!("/{}/{}", language, path.trim_start_matches('/'))
+            }
+            LocaleStrategy::Query(name) => {
+                let separator = if path.contains('?') { '&' } else { '?' };
+                format!("{}{}{}={}", path, separator, name, language)
+            }
+        }
+    }
+}
+
+/// Translates the static segments of a canonical path into a language.
+///
+/// The translation of a segment is the message of `{slugs}.{segment}`, looked up like
+/// [`YewI18n::t`]: in the catalogs, sources and binary catalogs of the provider, under its
+/// namespaces and in the enclosing providers. Segments without a translation, such as route
+/// parameters, are kept as they are. The query string, if any, is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+/// use yew_i18n::router::{canonicalize_slugs, localize_slugs};
+/// use yew_i18n::{Catalog, YewI18n};
+///
+/// let mut translations = HashMap::new();
+/// translations.insert(
+///     "fr".to_string(),
+///     Catalog::from([("routes", Catalog::from([("products", "produits")]))]),
+/// );
+/// let i18n = YewI18n::from_translations(vec!["en", "fr"], translations).unwrap();
+///
+/// assert_eq!(localize_slugs(&i18n, "routes", "/products/42", "fr"), "/produits/42");
+/// assert_eq!(canonicalize_slugs(&i18n, "routes", "/produits/42", "fr"), "/products/42");
+/// assert_eq!(localize_slugs(&i18n, "routes", "/products/42", "en"), "/products/42");
+///
+/// // Slugs of a source are found as well.
+/// let mut source = HashMap::new();
+/// source.insert("fr".to_string(), Catalog::from([("routes.cart", "panier")]));
+/// let i18n = YewI18n::from_source(vec!["en", "fr"], Arc::new(source)).unwrap();
+///
+/// assert_eq!(localize_slugs(&i18n, "routes", "/cart", "fr"), "/panier");
+/// assert_eq!(canonicalize_slugs(&i18n, "routes", "/panier", "fr"), "/cart");
+/// ```
+pub fn localize_slugs(i18n: &YewI18n, slugs: &str, path: &str, language: &str) -> String {
+    map_segments(path, |segment| slug(i18n, slugs, segment, language))
+}
+
+/// Translates the segments of a path localized in a language back to the canonical path
+/// recognized by the routes. This is the inverse of [`localize_slugs`].
+pub fn canonicalize_slugs(i18n: &YewI18n, slugs: &str, path: &str, language: &str) -> String {
+    map_segments(path, |segment| canonical(i18n, slugs, segment, language))
+}
+
+/// Returns the translation of a canonical segment.
+fn slug(i18n: &YewI18n, slugs: &str, segment: &str, language: &str) -> Option<String> {
+    if segment.is_empty() {
+        return None;
+    }
+    match i18n.find_message(language, &format!("{}.{}", slugs, segment))? {
+        Entry::Message(message) => Some(message.source().to_string()),
+        Entry::Text(message) => Some(message.into_owned()),
+    }
+}
+
+/// Returns the canonical segment of a translated slug.
+fn canonical(i18n: &YewI18n, slugs: &str, segment: &str, language: &str) -> Option<String> {
+    if segment.is_empty() {
+        return None;
+    }
+    let table = slugs.replacen(':', ".", 1);
+    i18n.keys_with_message(language, &table, segment)
+        .into_iter()
+        // Only segments whose slug resolves to this one, as for `localize_slugs`.
+        .find(|canonical| slug(i18n, slugs, canonical, language).as_deref() == Some(segment))
+}
+
+fn map_segments(path: &str, translate: impl Fn(&str) -> Option<String>) -> String {
+    let (path, query) = match path.split_once('?') {
+        Some((path, query)) => (path, format!("?{}", query)),
+        None => (path, String::new()),
+    };
+    let path: Vec<String> = path
+        .split('/')
+        .map(|segment| translate(segment).unwrap_or_else(|| segment.to_string()))
+        .collect();
+    format!("{}{}", path.join("/"), query)
+}
+
+/// How the routes below a [`LocalizedSwitch`] are localized, shared with links and hooks.
+#[derive(Clone, PartialEq, Default)]
+struct Localization {
+    strategy: LocaleStrategy,
+    slugs: Option<&'static str>,
+}
+
+impl Localization {
+    /// Returns the URL of a canonical path in a language, relative to the basename.
+    fn url(&self, i18n: &YewI18n, path: &str, language: &str) -> String {
+        let path = match self.slugs {
+            Some(slugs) => localize_slugs(i18n, slugs, path, language),
+            None => path.to_string(),
+        };
+        self.strategy.localize(&path, language)
+    }
+
+    /// Returns the canonical path of a path localized in a language.
+    fn canonical(&self, i18n: &YewI18n, path: &str, language: &str) -> String {
+        match self.slugs {
+            Some(slugs) => canonicalize_slugs(i18n, slugs, path, language),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// Properties of [`LocalizedSwitch`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct LocalizedSwitchProps<R>
+where
+    R: Routable,
+{
+    /// Callback which returns the `Html` to render for the current route.
+    pub render: Callback<R, Html>,
+    /// Where the locale is stored in the URL.
+    #[prop_or_default]
+    pub strategy: LocaleStrategy,
+    /// The catalog key of the translated route slugs, as in `/fr/produits/42` for the route
+    /// `/products/:id`. See [`localize_slugs`].
+    #[prop_or_default]
+    pub slugs: Option<&'static str>,
+}
+
+/// A `Switch` serving routes under a locale.
+///
+/// The language in the URL becomes the current language of the YewI18n context seen by the
+/// rendered route, and is then selected in the provider with
+/// [`YewI18n::select_language`], so components outside of the switch, such as navigation
+/// and `I18nHead`, follow it too. Effects do not run on the server: pass the language of the
+/// request URL, found with [`LocaleStrategy::split`], to the `language` prop of the provider.
+///
+/// URLs without a supported locale are rendered in the current language of the provider,
+/// and redirected to the same page under that locale once rendered in the browser.
+///
+/// # Examples
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use std::collections::HashMap;
+/// use yew::prelude::*;
+/// use yew_i18n::router::{LocaleStrategy, LocalizedSwitch};
+/// use yew_i18n::{use_translation, Catalog, I18nProvider};
+/// use yew_router::history::{AnyHistory, MemoryHistory};
+/// use yew_router::prelude::*;
+///
+/// #[derive(Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/")]
+///     Home,
+/// }
+///
+/// #[function_component]
+/// fn Title() -> Html {
+///     let i18n = use_translation();
+///     html! { <h1>{ i18n.t("title") }</h1> }
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct AppProps {
+///     url: AttrValue,
+/// }
+///
+/// #[function_component]
+/// fn App(props: &AppProps) -> Html {
+///     let mut translations = HashMap::new();
+///     translations.insert("en".to_string(), Catalog::from([("title", "Home")]));
+///     translations.insert("fr".to_string(), Catalog::from([("title", "Accueil")]));
+///     let (language, _) = LocaleStrategy::PathPrefix.split(&props.url, "", &["en", "fr"]);
+///     let history = AnyHistory::from(MemoryHistory::with_entries(vec![props.url.to_string()]));
+///
+///     html! {
+///         <I18nProvider supported_languages={vec!["en", "fr"]} translations={translations} language={language} suspense=true>
+///             <Router history={history}>
+///                 <nav><Title /></nav>
+///                 <LocalizedSwitch<Route> render={|route| match route {
+///                     Route::Home => html! { <main><Title /></main> },
+///                 }} />
+///             </Router>
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let render = |url: &str| {
+///     let props = AppProps { url: url.to_string().into() };
+///     yew::LocalServerRenderer::<App>::with_props(props).hydratable(false).render()
+/// };
+/// assert!(render("/fr/").await.contains("<nav><h1>Accueil</h1></nav><main><h1>Accueil</h1></main>"));
+/// assert!(render("/").await.contains("<nav><h1>Home</h1></nav><main><h1>Home</h1></main>"));
+/// # }));
+/// ```
+#[function_component]
+pub fn LocalizedSwitch<R>(props: &LocalizedSwitchProps<R>) -> Html
+where
+    R: Routable + 'static,
+{
+    let mut i18n = use_translation();
+    let navigator = use_navigator();
+    let localization = Localization {
+        strategy: props.strategy.clone(),
+        slugs: props.slugs,
+    };
+    let url = use_localized_url(&i18n, &localization.strategy);
+
+    let redirect = match &url {
+        Some((None, path)) => Some(localization.url(&i18n, path, i18n.current_language())),
+        _ => None,
+    };
+    use_effect_with(redirect, move |redirect| {
+        if let (Some(redirect), Some(navigator)) = (redirect, navigator) {
+            navigator.replace(&AnyRoute::new(redirect.clone()));
+        }
+    });
+
+    {
+        let mut i18n = i18n.clone();
+        let language = url.as_ref().and_then(|(language, _)| language.clone());
+        use_effect_with(language, move |language| {
+            if let Some(language) = language {
+                let _ = i18n.select_language(language);
+            }
+        });
+    }
+
+    let Some((language, path)) = url else {
+        return Html::default();
+    };
+    let path = match language {
+        Some(language) => {
+            let _ = i18n.set_translation_language(&language);
+            localization.canonical(&i18n, &path, &language)
+        }
+        None => path,
+    };
+
+    let pathname = path.split('?').next().unwrap_or_default();
+    let content = match R::recognize(pathname) {
+        Some(route) => props.render.emit(route),
+        None => Html::default(),
+    };
+
+    html! {
+        <ContextProvider<Localization> context={localization}>
+            <ContextProvider<YewI18n> context={i18n}>{ content }</ContextProvider<YewI18n>>
+        </ContextProvider<Localization>>
+    }
+}
+
+/// Properties of [`LocalizedLink`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct LocalizedLinkProps<R>
+where
+    R: Routable,
+{
+    /// The route to link to.
+    pub to: R,
+    /// The language of the target page. Defaults to the current language.
+    #[prop_or_default]
+    pub language: Option<AttrValue>,
+    /// Classes of the rendered anchor.
+    #[prop_or_default]
+    pub classes: Classes,
+    /// The content of the link.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A link to a route that keeps the current locale in the URL.
+#[function_component]
+pub fn LocalizedLink<R>(props: &LocalizedLinkProps<R>) -> Html
+where
+    R: Routable + 'static,
+{
+    let i18n = use_translation();
+    let navigator = use_navigator();
+    let localization = use_context::<Localization>().unwrap_or_default();
+
+    let language = props
+        .language
+        .as_deref()
+        .unwrap_or_else(|| i18n.current_language());
+    let path = localization.url(&i18n, &props.to.to_path(), language);
+    let href = with_basename(navigator.as_ref(), &path);
+
+    let onclick = Callback::from(move |event: MouseEvent| {
+        if let Some(navigator) = &navigator {
+            event.prevent_default();
+            navigator.push(&AnyRoute::new(path.clone()));
+        }
+    });
+
+    html! {
+        <a class={props.classes.clone()} href={href} onclick={onclick}>{ props.children.clone() }</a>
+    }
+}
+
+/// Returns the URL of the current page in another language, for language switchers and
+/// alternate links.
+///
+/// Translated slugs are translated into the target language. Returns `None` outside of a
+/// router.
+#[hook]
+pub fn use_alternate_url(language: &str) -> Option<String> {
+    let alternate_urls = use_alternate_urls();
+    alternate_urls.map(|alternate_urls| alternate_urls.emit(language.to_string()))
+}
+
+/// Returns a callback mapping a language to the URL of the current page in that language.
+///
+/// Pass it to the `alternate_url` prop of `I18nHead` to emit `hreflang` links for every
+/// supported language. Returns `None` outside of a router.
+#[hook]
+pub fn use_alternate_urls() -> Option<Callback<String, String>> {
+    let i18n = use_translation();
+    let navigator = use_navigator();
+    let localization = use_context::<Localization>().unwrap_or_default();
+    let url = use_localized_url(&i18n, &localization.strategy)?;
+
+    Some(Callback::from(move |language: String| {
+        let path = alternate_path(&i18n, &localization, url.clone(), &language);
+        with_basename(navigator.as_ref(), &path)
+    }))
+}
+
+/// Returns a callback navigating to the current page in another language.
+///
+/// Use it for language switchers inside a [`LocalizedSwitch`].
+#[hook]
+pub fn use_switch_language() -> Callback<String> {
+    let i18n = use_translation();
+    let navigator = use_navigator();
+    let localization = use_context::<Localization>().unwrap_or_default();
+    let url = use_localized_url(&i18n, &localization.strategy);
+
+    Callback::from(move |language: String| {
+        if let (Some(navigator), Some(url)) = (&navigator, url.clone()) {
+            let path = alternate_path(&i18n, &localization, url, &language);
+            navigator.push(&AnyRoute::new(path));
+        }
+    })
+}
+
+/// Returns the path of the current page in another language, relative to the basename.
+fn alternate_path(
+    i18n: &YewI18n,
+    localization: &Localization,
+    (current, path): (Option<String>, String),
+    language: &str,
+) -> String {
+    let path = match current {
+        Some(current) => localization.canonical(i18n, &path, &current),
+        None => path,
+    };
+    localization.url(i18n, &path, language)
+}
+
+fn with_basename(navigator: Option<&Navigator>, path: &str) -> String {
+    match navigator.and_then(|navigator| navigator.basename()) {
+        Some(basename) => format!("{}{}", basename, path),
+        None => path.to_string(),
+    }
+}
+
+/// Returns the locale of the current URL and the URL without it, relative to the basename.
+#[hook]
+fn use_localized_url(
+    i18n: &YewI18n,
+    strategy: &LocaleStrategy,
+) -> Option<(Option<String>, String)> {
+    let navigator = use_navigator();
+    let location = use_location();
+    let (navigator, location) = (navigator?, location?);
+
+    let path = location.path();
+    let path = match navigator.basename() {
+        Some(basename) => path.strip_prefix(basename).unwrap_or(path),
+        None => path,
+    };
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+
+    Some(strategy.split(&path, location.query_str(), i18n.supported_languages()))
+}
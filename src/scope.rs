@@ -0,0 +1,62 @@
+//! Scoped translation keys.
+//!
+//! An [`I18nScope`] prefixes the keys translated by its descendants, so a component deep in a
+//! checkout page calls `t("total")` instead of `t("checkout.summary.total")`, and can be moved
+//! to another section by changing a single prefix.
+
+use crate::{use_translation, YewI18n};
+use yew::prelude::*;
+
+/// Properties of [`I18nScope`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct I18nScopeProps {
+    /// The prefix of the keys translated by the children, appended to any enclosing scope.
+    /// A prefix starting with `/` replaces the enclosing scope.
+    pub prefix: AttrValue,
+    /// The components translating keys under the prefix.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Provides its children with a YewI18n context resolving relative keys under a prefix.
+///
+/// Keys starting with `/` are absolute and ignore the scope. See [`YewI18n::scoped`].
+///
+/// # Examples
+///
+/// ```
+/// use yew::prelude::*;
+/// use yew_i18n::{use_translation, I18nScope};
+///
+/// #[function_component]
+/// fn Total() -> Html {
+///     let i18n = use_translation();
+///     // Looks up "checkout.summary.total" and "common.ok".
+///     html! { <span>{ i18n.t("total") }{ i18n.t("/common.ok") }</span> }
+/// }
+///
+/// #[function_component]
+/// fn Summary() -> Html {
+///     html! {
+///         <I18nScope prefix="checkout.summary">
+///             <Total />
+///         </I18nScope>
+///     }
+/// }
+/// ```
+#[function_component]
+pub fn I18nScope(props: &I18nScopeProps) -> Html {
+    let i18n = use_scope(&props.prefix);
+
+    html! {
+        <ContextProvider<YewI18n> context={i18n}>{ props.children.clone() }</ContextProvider<YewI18n>>
+    }
+}
+
+/// Returns the YewI18n context resolving relative keys under a prefix.
+///
+/// The hook form of [`I18nScope`], for scoping the keys of a single component.
+#[hook]
+pub fn use_scope(prefix: &str) -> YewI18n {
+    use_translation().scoped(prefix)
+}
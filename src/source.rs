@@ -0,0 +1,215 @@
+//! Pluggable translation backends.
+//!
+//! A [`TranslationSource`] serves the messages of a backend other than the `translations`
+//! map: binary catalogs, Fluent or gettext bundles, IndexedDB, or anything else able to
+//! return a message for a key. Pass it to `I18nProvider` through the `source` prop, or to
+//! [`YewI18n::from_source`](crate::YewI18n::from_source).
+
+use crate::binary::BinaryCatalog;
+use crate::Catalog;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The catalog of a language loaded asynchronously.
+pub type CatalogFuture = Pin<Box<dyn Future<Output = Result<Catalog, String>>>>;
+
+/// A backend serving translated messages.
+///
+/// Sources are `Send` and `Sync`, so that a `YewI18n` reading from them can be shared between
+/// the threads of a server.
+///
+/// Keys are full `.`-separated keys, such as `nav.home`, with namespaces as their first
+/// segment (`checkout.title` for `checkout:title`). Scopes and namespaces are resolved by
+/// `YewI18n` before calling [`message`](Self::message).
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use std::sync::Arc;
+/// use yew_i18n::{TranslationSource, YewI18n};
+///
+/// /// Serves every key as itself, shouted.
+/// struct Shouting;
+///
+/// impl TranslationSource for Shouting {
+///     fn languages(&self) -> Vec<String> {
+///         vec!["en".to_string()]
+///     }
+///
+///     fn keys(&self, _language: &str) -> Vec<String> {
+///         Vec::new()
+///     }
+///
+///     fn message(&self, _language: &str, key: &str) -> Option<Cow<'_, str>> {
+///         Some(Cow::Owned(key.to_uppercase()))
+///     }
+/// }
+///
+/// let i18n = YewI18n::from_source(vec!["en"], Arc::new(Shouting)).unwrap();
+/// assert_eq!(i18n.t("hello"), "HELLO");
+/// ```
+pub trait TranslationSource: Send + Sync {
+    /// Returns the languages the source has messages for.
+    fn languages(&self) -> Vec<String>;
+
+    /// Returns the keys of a language.
+    fn keys(&self, language: &str) -> Vec<String>;
+
+    /// Returns the message of a key in a language.
+    fn message(&self, language: &str, key: &str) -> Option<Cow<'_, str>>;
+
+    /// Returns whether the source has messages for a language.
+    fn has_language(&self, language: &str) -> bool {
+        self.languages().iter().any(|known| known == language)
+    }
+
+    /// Returns whether a language has messages under a namespace.
+    fn has_namespace(&self, language: &str, namespace: &str) -> bool {
+        let prefix = format!("{}.", namespace);
+        self.keys(language)
+            .iter()
+            .any(|key| key.starts_with(&prefix))
+    }
+
+    /// Loads the catalog of a language the source has no messages for.
+    ///
+    /// `I18nProvider` calls it when the language is selected and the provider has no
+    /// `loader`, and merges the catalog into its translations. Returns `None` by default, for
+    /// sources holding every language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # #[cfg(feature = "ssr")]
+    /// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+    /// use std::borrow::Cow;
+    /// use std::sync::Arc;
+    /// use yew::prelude::*;
+    /// use yew_i18n::{use_translation, Catalog, CatalogFuture, I18nProvider, TranslationSource};
+    ///
+    /// /// Has no messages of its own, and loads every language on demand.
+    /// struct Remote;
+    ///
+    /// impl TranslationSource for Remote {
+    ///     fn languages(&self) -> Vec<String> {
+    ///         Vec::new()
+    ///     }
+    ///
+    ///     fn keys(&self, _language: &str) -> Vec<String> {
+    ///         Vec::new()
+    ///     }
+    ///
+    ///     fn message(&self, _language: &str, _key: &str) -> Option<Cow<'_, str>> {
+    ///         None
+    ///     }
+    ///
+    ///     fn load(&self, language: &str) -> Option<CatalogFuture> {
+    ///         let greeting = if language == "fr" { "Bonjour" } else { "Hello" };
+    ///         Some(Box::pin(async move { Ok(Catalog::from([("greeting", greeting)])) }))
+    ///     }
+    /// }
+    ///
+    /// #[function_component]
+    /// fn Greeting() -> Html {
+    ///     let i18n = use_translation();
+    ///     html! { <p>{ i18n.t("greeting") }</p> }
+    /// }
+    ///
+    /// #[function_component]
+    /// fn App() -> Html {
+    ///     let source: Arc<dyn TranslationSource> = Arc::new(Remote);
+    ///     html! {
+    ///         <I18nProvider supported_languages={vec!["en", "fr"]} language="fr" source={source} suspense=true>
+    ///             <Greeting />
+    ///         </I18nProvider>
+    ///     }
+    /// }
+    ///
+    /// let html = yew::LocalServerRenderer::<App>::new().hydratable(false).render().await;
+    /// assert!(html.contains("<p>Bonjour</p>"));
+    /// # }));
+    /// ```
+    fn load(&self, language: &str) -> Option<CatalogFuture> {
+        let _ = language;
+        None
+    }
+}
+
+impl PartialEq for dyn TranslationSource {
+    /// Compares sources by identity.
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+impl fmt::Debug for dyn TranslationSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TranslationSource")
+            .field("languages", &self.languages())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Serves in-memory catalogs, walking `.`-separated keys into nested tables.
+impl TranslationSource for HashMap<String, Catalog> {
+    fn languages(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+
+    fn keys(&self, language: &str) -> Vec<String> {
+        self.get(language)
+            .map(|catalog| crate::catalog::flatten(catalog).into_keys().collect())
+            .unwrap_or_default()
+    }
+
+    fn message(&self, language: &str, key: &str) -> Option<Cow<'_, str>> {
+        let catalog = self.get(language)?;
+        catalog
+            .get(key)
+            .or_else(|| {
+                key.split('.')
+                    .try_fold(catalog, |catalog, part| catalog.get(part))
+            })?
+            .as_message()
+            .map(Cow::Borrowed)
+    }
+
+    fn has_language(&self, language: &str) -> bool {
+        self.contains_key(language)
+    }
+
+    fn has_namespace(&self, language: &str, namespace: &str) -> bool {
+        self.get(language)
+            .and_then(|catalog| catalog.get(namespace))
+            .is_some()
+    }
+}
+
+impl TranslationSource for BinaryCatalog<'_> {
+    fn languages(&self) -> Vec<String> {
+        BinaryCatalog::languages(self).map(str::to_string).collect()
+    }
+
+    fn keys(&self, language: &str) -> Vec<String> {
+        BinaryCatalog::keys(self, language)
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn message(&self, language: &str, key: &str) -> Option<Cow<'_, str>> {
+        self.get(language, key).map(Cow::Borrowed)
+    }
+
+    fn has_language(&self, language: &str) -> bool {
+        self.contains_language(language)
+    }
+
+    fn has_namespace(&self, language: &str, namespace: &str) -> bool {
+        self.contains_prefix(language, &format!("{}.", namespace))
+    }
+}
@@ -0,0 +1,201 @@
+//! Rich-text translations with component interpolation.
+//!
+//! Messages may wrap parts of their text in tags, as in `Read the <link>terms</link>`. The
+//! [`Trans`] component renders each tag with the component supplied by the caller, so links
+//! and emphasis keep their place in the sentence in every language. Tags are never rendered
+//! as raw HTML: a tag without a matching component renders its content only.
+
+use crate::markup::{push_node, TextNode};
+use crate::{message, use_translation};
+use std::collections::HashMap;
+use yew::prelude::*;
+
+/// Properties of [`Trans`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct TransProps {
+    /// The key of the message to render. Named `message` since `key` is reserved by Yew.
+    pub message: AttrValue,
+    /// Renders the content of each tag of the message, by tag name.
+    #[prop_or_default]
+    pub components: HashMap<&'static str, Callback<Html, Html>>,
+    /// Arguments replacing the `{name}` placeholders of the message.
+    #[prop_or_default]
+    pub args: Vec<(&'static str, AttrValue)>,
+}
+
+/// Renders a translated message, replacing its tags with the given components.
+///
+/// Tags are written `<name>content</name>`, or `<name/>` for components without content.
+/// Unbalanced tags are rendered as text.
+///
+/// # Examples
+///
+/// ```
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # #[cfg(feature = "ssr")]
+/// # runtime.block_on(tokio::task::LocalSet::new().run_until(async {
+/// use std::collections::HashMap;
+/// use serde_json::json;
+/// use yew::prelude::*;
+/// use yew_i18n::{I18nProvider, Trans};
+///
+/// #[function_component]
+/// fn Terms() -> Html {
+///     let mut components = HashMap::new();
+///     components.insert("link", Callback::from(|content: Html| html! { <a href="/terms">{ content }</a> }));
+///     components.insert("b", Callback::from(|content: Html| html! { <strong>{ content }</strong> }));
+///
+///     html! {
+///         <p><Trans message="terms" components={components} args={vec![("action", "buying".into())]} /></p>
+///     }
+/// }
+///
+/// #[function_component]
+/// fn App() -> Html {
+///     let mut translations = HashMap::new();
+///     translations.insert(
+///         "en".to_string(),
+///         json!({ "terms": "Read the <link>terms</link> before <b>{action}</b>." }).into(),
+///     );
+///     html! {
+///         <I18nProvider supported_languages={vec!["en"]} translations={translations} suspense=true>
+///             <Terms />
+///         </I18nProvider>
+///     }
+/// }
+///
+/// let html = yew::LocalServerRenderer::<App>::new().hydratable(false).render().await;
+/// assert!(html.contains(r#"Read the <a href="/terms">terms</a> before <strong>buying</strong>."#));
+/// # }));
+/// ```
+#[function_component]
+pub fn Trans(props: &TransProps) -> Html {
+    let i18n = use_translation();
+    let args: Vec<(&str, &str)> = props
+        .args
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+
+    match i18n.lookup(&i18n.current_language, &props.message) {
+        Ok(text) => render(&parse(&text), &props.components, &args),
+        Err(err) => Html::from(err),
+    }
+}
+
+/// A parsed rich-text message.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Tag(String, Vec<Node>),
+}
+
+/// Parses the tags of a message into a tree, keeping unbalanced tags as text.
+fn parse(text: &str) -> Vec<Node> {
+    let mut stack: Vec<(String, Vec<Node>)> = vec![(String::new(), Vec::new())];
+    let mut rest = text;
+
+    while let Some(index) = rest.find('<') {
+        push_text(&mut stack, &rest[..index]);
+        let tail = &rest[index..];
+
+        rest = match tag(tail) {
+            Some((Tag::Open(name), after)) => {
+                stack.push((name.to_string(), Vec::new()));
+                after
+            }
+            Some((Tag::SelfClosing(name), after)) => {
+                let (_, nodes) = stack.last_mut().expect("the root is never popped");
+                nodes.push(Node::Tag(name.to_string(), Vec::new()));
+                after
+            }
+            Some((Tag::Close(name), after))
+                if stack.len() > 1 && stack.last().is_some_and(|(open, _)| open == name) =>
+            {
+                let (name, children) = stack.pop().expect("checked above");
+                let (_, nodes) = stack.last_mut().expect("the root is never popped");
+                nodes.push(Node::Tag(name, children));
+                after
+            }
+            _ => {
+                push_text(&mut stack, &tail[..1]);
+                &tail[1..]
+            }
+        };
+    }
+    push_text(&mut stack, rest);
+
+    // Tags left open are rendered as text.
+    while stack.len() > 1 {
+        let (name, children) = stack.pop().expect("checked above");
+        let (_, nodes) = stack.last_mut().expect("the root is never popped");
+        push_node(nodes, Node::Text(format!("<{}>", name)));
+        for child in children {
+            push_node(nodes, child);
+        }
+    }
+    stack.pop().map(|(_, nodes)| nodes).unwrap_or_default()
+}
+
+fn push_text(stack: &mut [(String, Vec<Node>)], text: &str) {
+    if let Some((_, nodes)) = stack.last_mut() {
+        push_node(nodes, Node::Text(text.to_string()));
+    }
+}
+
+impl TextNode for Node {
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Node::Text(text) => Some(text),
+            Node::Tag(..) => None,
+        }
+    }
+}
+
+enum Tag<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    SelfClosing(&'a str),
+}
+
+/// Parses a tag at the start of `text`, returning it and the rest.
+fn tag(text: &str) -> Option<(Tag<'_>, &str)> {
+    let inner = text.strip_prefix('<')?;
+    let end = inner.find('>')?;
+    let (content, after) = (&inner[..end], &inner[end + 1..]);
+    let valid = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    };
+
+    if let Some(name) = content.strip_prefix('/') {
+        valid(name).then_some((Tag::Close(name), after))
+    } else if let Some(name) = content.strip_suffix('/') {
+        let name = name.trim_end();
+        valid(name).then_some((Tag::SelfClosing(name), after))
+    } else {
+        valid(content).then_some((Tag::Open(content), after))
+    }
+}
+
+fn render(
+    nodes: &[Node],
+    components: &HashMap<&'static str, Callback<Html, Html>>,
+    args: &[(&str, &str)],
+) -> Html {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => Html::from(message::format(text, args).into_owned()),
+            Node::Tag(name, children) => {
+                let content = render(children, components, args);
+                match components.get(name.as_str()) {
+                    Some(component) => component.emit(content),
+                    None => content,
+                }
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,695 @@
+//! XLIFF 1.2 and 2.0 import and export of translation catalogs.
+//!
+//! This module lets you hand the `translations` map used by `I18nProvider` to
+//! professional translation tools and bring the translated units back. Nested tables are
+//! exported as dot-separated unit ids (`nav.home`) and written back to the same place on
+//! import.
+//!
+//! ```rust
+//! use yew_i18n::xliff::{XliffDocument, XliffVersion};
+//! use yew_i18n::Catalog;
+//! use std::collections::HashMap;
+//!
+//! let mut translations = HashMap::new();
+//! translations.insert("en".to_string(), Catalog::from([("greeting", "Hello")]));
+//! translations.insert("fr".to_string(), Catalog::default());
+//!
+//! let xml = XliffDocument::from_translations(&translations, "en", "fr")
+//!     .unwrap()
+//!     .with_version(XliffVersion::V2_0)
+//!     .to_xml();
+//!
+//! // ...the agency fills in the targets...
+//! let xml = xml
+//!     .replace("state=\"initial\"", "state=\"translated\"")
+//!     .replace("<source>Hello</source>", "<source>Hello</source><target>Bonjour</target>");
+//!
+//! XliffDocument::parse(&xml).unwrap().apply(&mut translations);
+//! let greeting = translations["fr"].get("greeting").and_then(|greeting| greeting.as_message());
+//! assert_eq!(greeting, Some("Bonjour"));
+//! ```
+
+use crate::Catalog;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The XLIFF specification version used when writing a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum XliffVersion {
+    /// XLIFF 1.2, still the most widely supported version in CAT tools.
+    #[default]
+    V1_2,
+    /// XLIFF 2.0.
+    V2_0,
+}
+
+/// The translation state of a unit, ordered from the least to the most advanced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum XliffState {
+    /// The unit has not been translated yet.
+    New,
+    /// The unit has been translated.
+    Translated,
+    /// The translation has been reviewed.
+    Reviewed,
+    /// The translation is final.
+    Final,
+}
+
+impl XliffState {
+    fn as_str(self, version: XliffVersion) -> &'static str {
+        match (self, version) {
+            (XliffState::New, XliffVersion::V1_2) => "needs-translation",
+            (XliffState::New, XliffVersion::V2_0) => "initial",
+            (XliffState::Translated, _) => "translated",
+            (XliffState::Reviewed, XliffVersion::V1_2) => "signed-off",
+            (XliffState::Reviewed, XliffVersion::V2_0) => "reviewed",
+            (XliffState::Final, _) => "final",
+        }
+    }
+
+    fn parse(state: &str) -> Option<Self> {
+        match state {
+            "new" | "initial" | "needs-translation" => Some(XliffState::New),
+            "translated"
+            | "needs-review-translation"
+            | "needs-adaptation"
+            | "needs-l10n"
+            | "needs-review-adaptation"
+            | "needs-review-l10n" => Some(XliffState::Translated),
+            "reviewed" | "signed-off" => Some(XliffState::Reviewed),
+            "final" => Some(XliffState::Final),
+            _ => None,
+        }
+    }
+}
+
+/// A single translatable message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XliffUnit {
+    /// The translation key, with nested keys joined by `.`.
+    pub id: String,
+    /// The message in the source language.
+    pub source: String,
+    /// The message in the target language, if already translated.
+    pub target: Option<String>,
+    /// A note for translators.
+    pub note: Option<String>,
+    /// The translation state of the unit.
+    pub state: Option<XliffState>,
+}
+
+/// An XLIFF document holding the units for one source and one target language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XliffDocument {
+    /// The XLIFF version used by `to_xml`.
+    pub version: XliffVersion,
+    /// The source language code.
+    pub source_language: String,
+    /// The target language code.
+    pub target_language: String,
+    /// The translatable units.
+    pub units: Vec<XliffUnit>,
+}
+
+impl XliffDocument {
+    /// Builds a document from a translations map.
+    ///
+    /// # Arguments
+    ///
+    /// * `translations` - Translations for different languages.
+    /// * `source_language` - The language whose messages are used as the source text.
+    /// * `target_language` - The language to translate into.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the document or an error message if the source language is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::xliff::{XliffDocument, XliffState};
+    /// use yew_i18n::Catalog;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut translations = HashMap::new();
+    /// translations.insert(
+    ///     "en".to_string(),
+    ///     Catalog::from([("nav", Catalog::from([("home", "Home"), ("about", "About")]))]),
+    /// );
+    /// translations.insert("fr".to_string(), Catalog::from([("nav", Catalog::from([("home", "Accueil")]))]));
+    ///
+    /// let document = XliffDocument::from_translations(&translations, "en", "fr").unwrap();
+    /// assert_eq!(document.units[1].id, "nav.home");
+    /// assert_eq!(document.units[1].target.as_deref(), Some("Accueil"));
+    /// assert_eq!(document.units[0].state, Some(XliffState::New));
+    /// ```
+    pub fn from_translations(
+        translations: &HashMap<String, Catalog>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Self, String> {
+        let source = translations.get(source_language).ok_or_else(|| {
+            format!(
+                "Unable to find translations for the source language '{}'",
+                source_language
+            )
+        })?;
+
+        let mut messages = Vec::new();
+        flatten(source, String::new(), &mut messages);
+
+        let target = translations.get(target_language);
+        let units = messages
+            .into_iter()
+            .map(|(id, source)| {
+                let target = target.and_then(|target| lookup(target, &id));
+                let state = Some(if target.is_some() {
+                    XliffState::Translated
+                } else {
+                    XliffState::New
+                });
+                XliffUnit {
+                    id,
+                    source,
+                    target,
+                    note: None,
+                    state,
+                }
+            })
+            .collect();
+
+        Ok(XliffDocument {
+            version: XliffVersion::default(),
+            source_language: source_language.to_string(),
+            target_language: target_language.to_string(),
+            units,
+        })
+    }
+
+    /// Sets the XLIFF version used when writing the document.
+    pub fn with_version(mut self, version: XliffVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Attaches translator notes to units, keyed by unit id.
+    pub fn with_notes(mut self, notes: &HashMap<String, String>) -> Self {
+        for unit in &mut self.units {
+            if let Some(note) = notes.get(&unit.id) {
+                unit.note = Some(note.clone());
+            }
+        }
+        self
+    }
+
+    /// Serializes the document to XLIFF markup.
+    pub fn to_xml(&self) -> String {
+        match self.version {
+            XliffVersion::V1_2 => self.to_xml_v1_2(),
+            XliffVersion::V2_0 => self.to_xml_v2_0(),
+        }
+    }
+
+    fn to_xml_v1_2(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n");
+        let _ = writeln!(
+            xml,
+            "  <file source-language=\"{}\" target-language=\"{}\" datatype=\"plaintext\" original=\"yew-i18n\">",
+            escape(&self.source_language),
+            escape(&self.target_language)
+        );
+        xml.push_str("    <body>\n");
+        for unit in &self.units {
+            let _ = writeln!(xml, "      <trans-unit id=\"{}\">", escape(&unit.id));
+            let _ = writeln!(xml, "        <source>{}</source>", escape(&unit.source));
+            if unit.target.is_some() || unit.state.is_some() {
+                let state = unit
+                    .state
+                    .map(|state| format!(" state=\"{}\"", state.as_str(self.version)))
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    xml,
+                    "        <target{}>{}</target>",
+                    state,
+                    escape(unit.target.as_deref().unwrap_or_default())
+                );
+            }
+            if let Some(note) = &unit.note {
+                let _ = writeln!(xml, "        <note>{}</note>", escape(note));
+            }
+            xml.push_str("      </trans-unit>\n");
+        }
+        xml.push_str("    </body>\n  </file>\n</xliff>\n");
+        xml
+    }
+
+    fn to_xml_v2_0(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<xliff version=\"2.0\" xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" srcLang=\"{}\" trgLang=\"{}\">",
+            escape(&self.source_language),
+            escape(&self.target_language)
+        );
+        xml.push_str("  <file id=\"yew-i18n\">\n");
+        for unit in &self.units {
+            let _ = writeln!(xml, "    <unit id=\"{}\">", escape(&unit.id));
+            if let Some(note) = &unit.note {
+                let _ = writeln!(xml, "      <notes><note>{}</note></notes>", escape(note));
+            }
+            let state = unit
+                .state
+                .map(|state| format!(" state=\"{}\"", state.as_str(self.version)))
+                .unwrap_or_default();
+            let _ = writeln!(xml, "      <segment{}>", state);
+            let _ = writeln!(xml, "        <source>{}</source>", escape(&unit.source));
+            if let Some(target) = &unit.target {
+                let _ = writeln!(xml, "        <target>{}</target>", escape(target));
+            }
+            xml.push_str("      </segment>\n    </unit>\n");
+        }
+        xml.push_str("  </file>\n</xliff>\n");
+        xml
+    }
+
+    /// Parses an XLIFF 1.2 or 2.0 document.
+    ///
+    /// # Arguments
+    ///
+    /// * `xml` - The XLIFF markup.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the parsed document or an error message if the markup is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::xliff::{XliffDocument, XliffState, XliffVersion};
+    ///
+    /// let xml = r#"<xliff version="1.2"><file source-language="en" target-language="de">
+    ///   <body><trans-unit id="greeting">
+    ///     <source>Hello</source><target state="final">Hallo</target>
+    ///   </trans-unit></body>
+    /// </file></xliff>"#;
+    ///
+    /// let document = XliffDocument::parse(xml).unwrap();
+    /// assert_eq!(document.version, XliffVersion::V1_2);
+    /// assert_eq!(document.target_language, "de");
+    /// assert_eq!(document.units[0].target.as_deref(), Some("Hallo"));
+    /// assert_eq!(document.units[0].state, Some(XliffState::Final));
+    /// ```
+    ///
+    /// Alternative translations, such as machine translation suggestions in `alt-trans`, are
+    /// ignored:
+    ///
+    /// ```
+    /// use yew_i18n::xliff::{XliffDocument, XliffState};
+    ///
+    /// let xml = r#"<xliff version="1.2"><file source-language="en" target-language="de">
+    ///   <body><trans-unit id="greeting">
+    ///     <source>Hello</source><target state="translated">Hallo</target>
+    ///     <alt-trans origin="mt"><source>Hello</source><target state="new">Guten Tag</target></alt-trans>
+    ///   </trans-unit></body>
+    /// </file></xliff>"#;
+    ///
+    /// let unit = &XliffDocument::parse(xml).unwrap().units[0];
+    /// assert_eq!(unit.target.as_deref(), Some("Hallo"));
+    /// assert_eq!(unit.state, Some(XliffState::Translated));
+    /// ```
+    ///
+    /// The segments of an XLIFF 2.0 unit are joined in order, and the unit is only translated
+    /// when all of them are:
+    ///
+    /// ```
+    /// use yew_i18n::xliff::{XliffDocument, XliffState};
+    ///
+    /// let xml = r#"<xliff version="2.0" srcLang="en" trgLang="de"><file id="f1">
+    ///   <unit id="intro">
+    ///     <segment state="final"><source>Hello.</source><target>Hallo.</target></segment>
+    ///     <ignorable><source> </source></ignorable>
+    ///     <segment state="translated"><source>Welcome!</source><target>Willkommen!</target></segment>
+    ///   </unit>
+    ///   <unit id="outro">
+    ///     <segment><source>Bye.</source><target>Tschüss.</target></segment>
+    ///     <segment><source>See you!</source></segment>
+    ///   </unit>
+    /// </file></xliff>"#;
+    ///
+    /// let document = XliffDocument::parse(xml).unwrap();
+    /// assert_eq!(document.units[0].source, "Hello. Welcome!");
+    /// assert_eq!(document.units[0].target.as_deref(), Some("Hallo. Willkommen!"));
+    /// assert_eq!(document.units[0].state, Some(XliffState::Translated));
+    /// assert_eq!(document.units[1].target, None);
+    /// ```
+    ///
+    /// Inline elements, such as `<g>`, `<ph>`, `<x>`, `<pc>` or `<mrk>`, have no equivalent in
+    /// the catalogs, so messages holding them are rejected rather than flattened to their text:
+    ///
+    /// ```
+    /// use yew_i18n::xliff::XliffDocument;
+    ///
+    /// let xml = r#"<xliff version="1.2"><file source-language="en" target-language="de">
+    ///   <body><trans-unit id="greeting">
+    ///     <source>Hello <ph id="1">{name}</ph></source><target>Hallo <ph id="1">{name}</ph></target>
+    ///   </trans-unit></body>
+    /// </file></xliff>"#;
+    ///
+    /// assert!(XliffDocument::parse(xml).is_err());
+    /// assert!(XliffDocument::parse(&xml.replace(r#"<ph id="1">{name}</ph>"#, r#"<mrk id="m1" type="term">{name}</mrk>"#)).is_err());
+    /// ```
+    pub fn parse(xml: &str) -> Result<Self, String> {
+        let mut reader = Reader::from_str(xml);
+        let mut document = XliffDocument {
+            version: XliffVersion::default(),
+            source_language: String::new(),
+            target_language: String::new(),
+            units: Vec::new(),
+        };
+        let mut unit: Option<XliffUnit> = None;
+        // The segment or ignorable part being read, the whole unit in XLIFF 1.2.
+        let mut part: Option<Part> = None;
+        // Whether every segment read so far has a target.
+        let mut translated = true;
+        let mut text: Option<String> = None;
+        // Depth of the alternative translations being read, whose source, target and state
+        // are suggestions rather than the content of the unit.
+        let mut alternatives = 0usize;
+
+        loop {
+            let event = reader
+                .read_event()
+                .map_err(|err| format!("Invalid XLIFF document: {}", err))?;
+            match event {
+                Event::Start(ref element) if is_alternative(element.local_name().as_ref()) => {
+                    alternatives += 1;
+                }
+                Event::End(ref element) if is_alternative(element.local_name().as_ref()) => {
+                    alternatives = alternatives.saturating_sub(1);
+                }
+                _ if alternatives > 0 => {}
+                Event::Start(ref element) | Event::Empty(ref element) => {
+                    let name = element.local_name();
+                    match name.as_ref() {
+                        b"xliff" => {
+                            if attribute(element, "version")?.as_deref() == Some("2.0") {
+                                document.version = XliffVersion::V2_0;
+                            }
+                            if let Some(lang) = attribute(element, "srcLang")? {
+                                document.source_language = lang;
+                            }
+                            if let Some(lang) = attribute(element, "trgLang")? {
+                                document.target_language = lang;
+                            }
+                        }
+                        b"file" => {
+                            if let Some(lang) = attribute(element, "source-language")? {
+                                document.source_language = lang;
+                            }
+                            if let Some(lang) = attribute(element, "target-language")? {
+                                document.target_language = lang;
+                            }
+                        }
+                        b"trans-unit" | b"unit" => {
+                            let id = attribute(element, "id")?
+                                .ok_or_else(|| "Found a unit without an id".to_string())?;
+                            unit = Some(XliffUnit {
+                                id,
+                                source: String::new(),
+                                target: None,
+                                note: None,
+                                state: None,
+                            });
+                            translated = true;
+                            // XLIFF 1.2 units hold their source and target directly.
+                            part = (name.as_ref() == b"trans-unit").then(Part::default);
+                        }
+                        b"segment" | b"ignorable" | b"target" => {
+                            if let (Some(unit), Some(state)) =
+                                (unit.as_mut(), attribute(element, "state")?)
+                            {
+                                // A unit is only as advanced as its least advanced segment.
+                                if let Some(state) = XliffState::parse(&state) {
+                                    unit.state = Some(unit.state.map_or(state, |s| s.min(state)));
+                                }
+                            }
+                            match name.as_ref() {
+                                b"target" => {
+                                    if matches!(event, Event::Start(_)) {
+                                        text = Some(String::new());
+                                    }
+                                }
+                                kind if matches!(event, Event::Start(_)) => {
+                                    part = Some(Part {
+                                        ignorable: kind == b"ignorable",
+                                        ..Part::default()
+                                    });
+                                }
+                                _ => {}
+                            }
+                        }
+                        b"source" | b"note" if matches!(event, Event::Start(_)) => {
+                            text = Some(String::new());
+                        }
+                        name if text.is_some() && is_inline(name) => {
+                            return Err(format!(
+                                "Unit '{}' holds an unsupported inline element <{}>",
+                                unit.as_ref().map_or("", |unit| unit.id.as_str()),
+                                String::from_utf8_lossy(name)
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(content) => {
+                    if let Some(text) = text.as_mut() {
+                        let content = content
+                            .unescape()
+                            .map_err(|err| format!("Invalid XLIFF document: {}", err))?;
+                        text.push_str(&content);
+                    }
+                }
+                Event::CData(content) => {
+                    if let Some(text) = text.as_mut() {
+                        text.push_str(&String::from_utf8_lossy(&content));
+                    }
+                }
+                Event::End(element) => {
+                    let name = element.local_name();
+                    match name.as_ref() {
+                        b"source" | b"target" => {
+                            if let (Some(part), Some(content)) = (part.as_mut(), text.take()) {
+                                match name.as_ref() {
+                                    b"source" => part.source = Some(content),
+                                    _ if content.is_empty() => {}
+                                    _ => part.target = Some(content),
+                                }
+                            }
+                        }
+                        b"note" => {
+                            if let (Some(unit), Some(content)) = (unit.as_mut(), text.take()) {
+                                unit.note = Some(content);
+                            }
+                        }
+                        b"segment" | b"ignorable" => {
+                            if let (Some(unit), Some(part)) = (unit.as_mut(), part.take()) {
+                                part.append_to(unit, &mut translated);
+                            }
+                        }
+                        b"trans-unit" | b"unit" => {
+                            if let Some(mut unit) = unit.take() {
+                                if let Some(part) = part.take() {
+                                    part.append_to(&mut unit, &mut translated);
+                                }
+                                if !translated {
+                                    unit.target = None;
+                                }
+                                document.units.push(unit);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if document.target_language.is_empty() {
+            return Err("The XLIFF document does not declare a target language".to_string());
+        }
+
+        Ok(document)
+    }
+
+    /// Writes the translated units back into a translations map.
+    ///
+    /// Units without a target, and units whose state is [`XliffState::New`] (`new`,
+    /// `needs-translation` or `initial`), are skipped. Ids that exist as a top-level key in the source
+    /// language are written as flat keys, other ids are split on `.` into nested tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_i18n::xliff::XliffDocument;
+    /// use std::collections::HashMap;
+    ///
+    /// let xml = r#"<xliff version="1.2"><file source-language="en" target-language="de"><body>
+    ///   <trans-unit id="greeting"><source>Hello</source><target state="final">Hallo</target></trans-unit>
+    ///   <trans-unit id="farewell"><source>Bye</source><target state="needs-translation">Bye</target></trans-unit>
+    /// </body></file></xliff>"#;
+    ///
+    /// let mut translations = HashMap::new();
+    /// XliffDocument::parse(xml).unwrap().apply(&mut translations);
+    /// assert!(translations["de"].get("greeting").is_some());
+    /// assert!(translations["de"].get("farewell").is_none());
+    /// ```
+    pub fn apply(&self, translations: &mut HashMap<String, Catalog>) {
+        let flat_keys: Vec<String> = translations
+            .get(&self.source_language)
+            .and_then(Catalog::as_table)
+            .map(|source| source.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let target = translations
+            .entry(self.target_language.clone())
+            .or_default();
+
+        for unit in &self.units {
+            let Some(message) = &unit.target else {
+                continue;
+            };
+            if unit.state == Some(XliffState::New) {
+                continue;
+            }
+            let path: Vec<&str> = if flat_keys.contains(&unit.id) {
+                vec![unit.id.as_str()]
+            } else {
+                unit.id.split('.').collect()
+            };
+            insert(target, &path, Catalog::Message(message.clone()));
+        }
+    }
+}
+
+/// Returns whether an element holds alternative translations: XLIFF 1.2 `alt-trans`, or
+/// XLIFF 2.0 translation candidates.
+fn is_alternative(name: &[u8]) -> bool {
+    matches!(name, b"alt-trans" | b"match")
+}
+
+/// Returns whether an element is inline markup of XLIFF 1.2 or 2.0, such as formatting codes,
+/// placeholders or annotations.
+fn is_inline(name: &[u8]) -> bool {
+    matches!(
+        name,
+        b"g" | b"x"
+            | b"bx"
+            | b"ex"
+            | b"ph"
+            | b"bpt"
+            | b"ept"
+            | b"it"
+            | b"sub"
+            | b"pc"
+            | b"sc"
+            | b"ec"
+            | b"mrk"
+            | b"sm"
+            | b"em"
+    )
+}
+
+/// The source and target of an XLIFF 2.0 segment or ignorable part, or of a whole XLIFF 1.2
+/// unit.
+#[derive(Default)]
+struct Part {
+    ignorable: bool,
+    source: Option<String>,
+    target: Option<String>,
+}
+
+impl Part {
+    /// Appends the part to the unit. Ignorable parts without a target, usually whitespace
+    /// between sentences, keep their source in the target.
+    fn append_to(self, unit: &mut XliffUnit, translated: &mut bool) {
+        let target = match (self.target, self.ignorable) {
+            (Some(target), _) => Some(target),
+            (None, true) => self.source.clone(),
+            (None, false) => None,
+        };
+        unit.source
+            .push_str(self.source.as_deref().unwrap_or_default());
+        match target {
+            Some(target) => unit
+                .target
+                .get_or_insert_with(String::new)
+                .push_str(&target),
+            None => *translated = false,
+        }
+    }
+}
+
+fn flatten(catalog: &Catalog, prefix: String, messages: &mut Vec<(String, String)>) {
+    match catalog {
+        Catalog::Table(table) => {
+            for (key, catalog) in table {
+                let id = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(catalog, id, messages);
+            }
+        }
+        Catalog::Message(message) => messages.push((prefix, message.clone())),
+    }
+}
+
+fn lookup(catalog: &Catalog, id: &str) -> Option<String> {
+    catalog
+        .get(id)
+        .or_else(|| {
+            id.split('.')
+                .try_fold(catalog, |catalog, key| catalog.get(key))
+        })?
+        .as_message()
+        .map(str::to_string)
+}
+
+fn insert(catalog: &mut Catalog, path: &[&str], message: Catalog) {
+    let table = catalog.table_mut();
+    match path {
+        [] => {}
+        [key] => {
+            table.insert(key.to_string(), message);
+        }
+        [key, rest @ ..] => {
+            let child = table.entry(key.to_string()).or_default();
+            insert(child, rest, message);
+        }
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|err| format!("Invalid XLIFF document: {}", err))?;
+        if attribute.key.local_name().as_ref() == name.as_bytes() {
+            let value = attribute
+                .unescape_value()
+                .map_err(|err| format!("Invalid XLIFF document: {}", err))?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn escape(text: &str) -> String {
+    quick_xml::escape::escape(text).into_owned()
+}
@@ -0,0 +1,64 @@
+//! Hydrates a page whose head was rendered on the server and checks that `I18nHead` replaces
+//! the server's alternate links.
+//!
+//! Run with `wasm-pack test --headless --firefox -- --test head`.
+#![cfg(target_arch = "wasm32")]
+
+use std::collections::HashMap;
+use wasm_bindgen_test::*;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_i18n::{
+    render_head, Catalog, I18nHead, I18nHeadProps, I18nProvider, Translations, YewI18n,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn head_props() -> I18nHeadProps {
+    I18nHeadProps {
+        title: Some("title".into()),
+        description: None,
+        alternate_url: Some(Callback::from(|language: String| format!("/{}/", language))),
+        base_url: Some("https://example.com".into()),
+    }
+}
+
+fn translations() -> HashMap<String, Catalog> {
+    [("en", "Home"), ("fr", "Accueil")]
+        .into_iter()
+        .map(|(language, title)| (language.to_string(), Catalog::from([("title", title)])))
+        .collect()
+}
+
+#[function_component]
+fn App() -> Html {
+    html! {
+        <I18nProvider
+            supported_languages={vec!["en", "fr"]}
+            translations={Translations::from(translations())}
+        >
+            <I18nHead ..head_props() />
+        </I18nProvider>
+    }
+}
+
+#[wasm_bindgen_test]
+async fn hydrated_head_keeps_one_set_of_alternate_links() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let head = document.query_selector("head").unwrap().unwrap();
+
+    let i18n = YewI18n::from_translations(vec!["en", "fr"], translations()).unwrap();
+    head.insert_adjacent_html("beforeend", &render_head(&i18n, &head_props()))
+        .unwrap();
+
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+    yew::Renderer::<App>::with_root(root).render();
+    sleep(std::time::Duration::ZERO).await;
+
+    let links = document
+        .query_selector_all(r#"link[rel="alternate"][hreflang]"#)
+        .unwrap();
+    // `x-default`, `en` and `fr`.
+    assert_eq!(links.length(), 3);
+}
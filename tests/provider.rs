@@ -0,0 +1,202 @@
+//! Renders `I18nProvider` in the browser and checks that its context follows new props.
+//!
+//! Run with `wasm-pack test --headless --firefox -- --test provider`.
+#![cfg(target_arch = "wasm32")]
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use wasm_bindgen_test::*;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_i18n::{use_translation, Catalog, I18nProvider, TranslationLoader, Translations};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Properties, PartialEq, Clone)]
+struct AppProps {
+    supported_languages: Vec<&'static str>,
+    translations: Translations,
+    language: Option<AttrValue>,
+}
+
+#[function_component]
+fn Greeting() -> Html {
+    let i18n = use_translation();
+    html! { <p>{ format!("{}: {}", i18n.current_language(), i18n.t("hello")) }</p> }
+}
+
+#[function_component]
+fn App(props: &AppProps) -> Html {
+    html! {
+        <I18nProvider
+            supported_languages={props.supported_languages.clone()}
+            translations={props.translations.clone()}
+            language={props.language.clone()}
+        >
+            <Greeting />
+        </I18nProvider>
+    }
+}
+
+fn translations(messages: &[(&str, &str)]) -> Translations {
+    messages
+        .iter()
+        .map(|(language, hello)| (language.to_string(), Catalog::from([("hello", *hello)])))
+        .collect::<HashMap<_, _>>()
+        .into()
+}
+
+/// Returns the text rendered once pending renders have run.
+async fn rendered(root: &web_sys::Element) -> String {
+    sleep(std::time::Duration::ZERO).await;
+    root.text_content().unwrap_or_default()
+}
+
+#[wasm_bindgen_test]
+async fn provider_follows_new_props() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let mut props = AppProps {
+        supported_languages: vec!["en", "fr"],
+        translations: translations(&[("en", "Hello"), ("fr", "Bonjour")]),
+        language: None,
+    };
+    let mut app = yew::Renderer::<App>::with_root_and_props(root.clone(), props.clone()).render();
+    assert_eq!(rendered(&root).await, "en: Hello");
+
+    props.translations = translations(&[("en", "Hi"), ("fr", "Salut")]);
+    app.update(props.clone());
+    assert_eq!(rendered(&root).await, "en: Hi");
+
+    props.language = Some("fr".into());
+    app.update(props.clone());
+    assert_eq!(rendered(&root).await, "fr: Salut");
+
+    // The current language is kept while it is still supported.
+    props.supported_languages = vec!["de", "fr"];
+    props.translations = translations(&[("de", "Hallo"), ("fr", "Salut")]);
+    app.update(props.clone());
+    assert_eq!(rendered(&root).await, "fr: Salut");
+
+    props.supported_languages = vec!["de"];
+    app.update(props);
+    assert_eq!(rendered(&root).await, "de: Hallo");
+}
+
+#[derive(Properties, PartialEq, Clone)]
+struct LoaderAppProps {
+    loader: TranslationLoader,
+}
+
+#[function_component]
+fn LoaderApp(props: &LoaderAppProps) -> Html {
+    html! {
+        <I18nProvider
+            supported_languages={vec!["en", "fr"]}
+            language="fr"
+            loader={props.loader.clone()}
+            suspense=true
+        >
+            <Greeting />
+        </I18nProvider>
+    }
+}
+
+#[wasm_bindgen_test]
+async fn provider_requests_catalogs_again_from_a_new_loader() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    // The first loader never answers.
+    let props = LoaderAppProps {
+        loader: TranslationLoader::new(|_| std::future::pending()),
+    };
+    let mut app = yew::Renderer::<LoaderApp>::with_root_and_props(root.clone(), props).render();
+    assert_eq!(rendered(&root).await, "");
+
+    app.update(LoaderAppProps {
+        loader: TranslationLoader::new(|_| async { Ok(Catalog::from([("hello", "Salut")])) }),
+    });
+    assert_eq!(rendered(&root).await, "fr: Salut");
+}
+
+#[wasm_bindgen_test]
+async fn provider_drops_catalogs_of_a_replaced_loader() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    // The first loader answers after the second one.
+    let props = LoaderAppProps {
+        loader: TranslationLoader::new(|_| async {
+            sleep(std::time::Duration::from_millis(50)).await;
+            Ok(Catalog::from([("hello", "Bonjour")]))
+        }),
+    };
+    let mut app = yew::Renderer::<LoaderApp>::with_root_and_props(root.clone(), props).render();
+    assert_eq!(rendered(&root).await, "");
+
+    app.update(LoaderAppProps {
+        loader: TranslationLoader::new(|_| async { Ok(Catalog::from([("hello", "Salut")])) }),
+    });
+    assert_eq!(rendered(&root).await, "fr: Salut");
+
+    sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(rendered(&root).await, "fr: Salut");
+}
+
+thread_local! {
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[function_component]
+fn CountingGreeting() -> Html {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let i18n = use_translation();
+    html! { <p>{ i18n.t("hello") }</p> }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+struct CallbackAppProps {
+    renders: usize,
+}
+
+#[function_component]
+fn CallbackApp(_props: &CallbackAppProps) -> Html {
+    let translations = use_memo((), |_| translations(&[("en", "Hello")]));
+    let loader = use_memo((), |_| {
+        TranslationLoader::new(|_| async { Err("Not found".to_string()) })
+    });
+    html! {
+        <I18nProvider
+            supported_languages={vec!["en"]}
+            translations={(*translations).clone()}
+            loader={(*loader).clone()}
+            on_error={Callback::from(|_: String| ())}
+        >
+            <CountingGreeting />
+        </I18nProvider>
+    }
+}
+
+#[wasm_bindgen_test]
+async fn provider_keeps_its_context_for_a_new_on_error_callback() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let mut app = yew::Renderer::<CallbackApp>::with_root_and_props(
+        root.clone(),
+        CallbackAppProps { renders: 0 },
+    )
+    .render();
+    assert_eq!(rendered(&root).await, "Hello");
+    let renders = RENDERS.with(Cell::get);
+
+    app.update(CallbackAppProps { renders: 1 });
+    assert_eq!(rendered(&root).await, "Hello");
+    assert_eq!(RENDERS.with(Cell::get), renders);
+}